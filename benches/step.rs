@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate criterion;
+extern crate prugel;
+extern crate rand;
+
+use criterion::{black_box, Criterion};
+use prugel::{Card, Game, Hand, RandomStrategy, StepOutcome, Suit};
+use rand::prng::XorShiftRng;
+use rand::SeedableRng;
+
+fn step(c: &mut Criterion) {
+    let mut strategy = RandomStrategy::from_seed([60; 16]);
+    let mut g = Game::new(5, 3);
+    g.set_rng(XorShiftRng::from_seed([42; 16]));
+    g.shuffle();
+    c.bench_function("step", |b| b.iter(|| {
+        // Start over now and then so the history does not grow without bound.
+        if g.round() >= 10_000 {
+            g.reset();
+        }
+        match g.step(&mut strategy).expect("random strategy only picks eligible players") {
+            StepOutcome::Played(result) => { black_box(result); },
+            StepOutcome::Exhausted | StepOutcome::Stalemate => g.reset(),
+        }
+    }));
+}
+
+fn hand_sum(c: &mut Criterion) {
+    let mut hand = Hand::new();
+    hand.accept(Card::Regular(Suit::Spades, 1));
+    hand.accept(Card::Regular(Suit::Hearts, 7));
+    hand.accept(Card::Regular(Suit::Clubs, 3));
+    hand.accept(Card::Regular(Suit::Diamonds, 2));
+    c.bench_function("hand_sum", |b| b.iter(|| black_box(&hand).hand_sum()));
+}
+
+criterion_group!(benches, step, hand_sum);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use std::error;
+use std::fmt;
+
+use rand::SeedableRng;
+use rand::prng::XorShiftRng;
+
+use game::Game;
+use rules::GameRules;
+use seed::seed_bytes;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    TooFewPlayers(usize),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BuildError::TooFewPlayers(n) => write!(f, "need at least 2 players, got {}", n),
+        }
+    }
+}
+
+impl error::Error for BuildError {}
+
+/// Builds a `Game`. With a `seed`, the game's RNG is seeded from it and the
+/// deck is shuffled; otherwise the deck is left in its unshuffled order.
+#[derive(Debug, Clone)]
+pub struct GameBuilder {
+    players: usize,
+    decks: usize,
+    jokers: u8,
+    rules: GameRules,
+    seed: Option<u64>,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        GameBuilder {
+            players: 0,
+            decks: 1,
+            jokers: 0,
+            rules: GameRules::default(),
+            seed: None,
+        }
+    }
+}
+
+impl GameBuilder {
+    pub fn new() -> Self {
+        GameBuilder::default()
+    }
+
+    pub fn players(mut self, players: usize) -> Self {
+        self.players = players;
+        self
+    }
+
+    /// Number of full 52-card sets to shuffle together; defaults to 1.
+    pub fn decks(mut self, decks: usize) -> Self {
+        self.decks = decks;
+        self
+    }
+
+    pub fn jokers(mut self, jokers: u8) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    pub fn rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<Game, BuildError> {
+        if self.players < 2 {
+            return Err(BuildError::TooFewPlayers(self.players));
+        }
+        let mut game = Game::with_rules(self.players, self.decks, self.jokers, self.rules);
+        if let Some(seed) = self.seed {
+            game.set_rng(XorShiftRng::from_seed(seed_bytes(seed)));
+            game.shuffle();
+        }
+        Ok(game)
+    }
+}
@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+use std::error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Clubs,
+    Diamonds,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Color {
+    Red,
+    Black,
+}
+
+impl Suit {
+    /// The four suits in declaration order, which is also their `Ord` order.
+    pub fn all() -> [Suit; 4] {
+        [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds]
+    }
+
+    pub fn is_red(&self) -> bool {
+        *self == Suit::Hearts || *self == Suit::Diamonds
+    }
+
+    pub fn color(&self) -> Color {
+        if self.is_red() { Color::Red } else { Color::Black }
+    }
+
+    pub fn to_char(&self) -> char {
+        match *self {
+            Suit::Spades => '\u{2660}',
+            Suit::Hearts => '\u{2665}',
+            Suit::Clubs => '\u{2663}',
+            Suit::Diamonds => '\u{2666}',
+        }
+    }
+
+    /// Accepts both the suit glyphs and the ASCII letters `S`, `H`, `C`, `D`.
+    pub fn from_char(c: char) -> Option<Suit> {
+        match c {
+            '\u{2660}' | 'S' => Some(Suit::Spades),
+            '\u{2665}' | 'H' => Some(Suit::Hearts),
+            '\u{2663}' | 'C' => Some(Suit::Clubs),
+            '\u{2666}' | 'D' => Some(Suit::Diamonds),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Cards are ordered by suit in declaration order (spades, hearts, clubs,
+/// diamonds), then by rank from ace to king, with each special card in the
+/// slot of its suit and rank (just after a regular card of the same suit and
+/// rank, should both exist). Jokers come after all suited cards, ordered by
+/// index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Card {
+    Regular(Suit, u8),
+    Joker(u8),
+    /// A card that wins outright; see `GameRules::specials`.
+    Special(Suit, u8),
+}
+
+/// The Queen of Diamonds and the Jack of Spades.
+pub const DEFAULT_SPECIALS: [(Suit, u8); 2] = [(Suit::Diamonds, 12), (Suit::Spades, 11)];
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Card::Regular(s, n) | Card::Special(s, n) => match n {
+                1 => write!(f, "{}A", s),
+                13 => write!(f, "{}K", s),
+                12 => write!(f, "{}Q", s),
+                11 => write!(f, "{}J", s),
+                10 => write!(f, "{}T", s),
+                _ => write!(f, "{}{}", s, n),
+            },
+            Card::Joker(n) => write!(f, "J{}", n),
+        }
+    }
+}
+
+impl Card {
+    fn sort_key(&self) -> (u8, u8, bool) {
+        match *self {
+            Card::Regular(s, n) => (s as u8, n, false),
+            Card::Special(s, n) => (s as u8, n, true),
+            Card::Joker(n) => (4, n, false),
+        }
+    }
+
+    /// Panics unless `1 <= num <= 13`; see `try_new`.
+    pub fn new(suit: Suit, num: u8) -> Self {
+        Card::with_specials(suit, num, &DEFAULT_SPECIALS)
+    }
+
+    pub fn try_new(suit: Suit, num: u8) -> Result<Self, CardError> {
+        Card::try_with_specials(suit, num, &DEFAULT_SPECIALS)
+    }
+
+    /// The rank of a regular card, from 1 for ace to 13 for king. For
+    /// `Joker(n)` this is the joker's index `n`, which only tells jokers
+    /// apart and says nothing about its value. Special cards never count
+    /// towards a hand's sum and have no rank.
+    pub fn rank(&self) -> Option<u8> {
+        match *self {
+            Card::Regular(_, n) | Card::Joker(n) => Some(n),
+            Card::Special(..) => None,
+        }
+    }
+
+    /// The suit of a regular or special card; jokers have no suit.
+    pub fn suit(&self) -> Option<Suit> {
+        match *self {
+            Card::Regular(s, _) | Card::Special(s, _) => Some(s),
+            Card::Joker(_) => None,
+        }
+    }
+
+    pub fn is_regular(&self) -> bool {
+        matches!(*self, Card::Regular(..))
+    }
+
+    pub fn is_joker(&self) -> bool {
+        matches!(*self, Card::Joker(_))
+    }
+
+    pub fn is_special(&self) -> bool {
+        matches!(*self, Card::Special(..))
+    }
+
+    /// The color of the card's suit, special cards included; jokers have
+    /// no color. Unlike `is_red` this says nothing about routing.
+    pub fn color(&self) -> Option<Color> {
+        self.suit().map(|s| s.color())
+    }
+
+    /// Whether the card is a regular card of a red suit. Jokers and special
+    /// cards are never red, whatever their suit.
+    pub fn is_red(&self) -> bool {
+        match *self {
+            Card::Regular(s, _) => s.is_red(),
+            Card::Joker(_) | Card::Special(..) => false,
+        }
+    }
+
+    /// Like `to_string`, but with `color` red suits are wrapped in ANSI
+    /// escape codes for terminal output.
+    pub fn render(&self, color: bool) -> String {
+        match *self {
+            Card::Regular(s, _) | Card::Special(s, _) if color && s.is_red() =>
+                format!("\x1b[31m{}\x1b[0m", self),
+            _ => self.to_string(),
+        }
+    }
+
+    pub fn with_specials(suit: Suit, num: u8, specials: &[(Suit, u8)]) -> Self {
+        Card::try_with_specials(suit, num, specials).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// A dense index for lookup tables. The 52 suited cards take 0 to 51,
+    /// `13 * suit + rank - 1` with suits in the order of `Suit::all`, so a
+    /// special card shares the slot of the regular card it replaces.
+    /// `Joker(n)` is `52 + n`, so a table for a deck with `j` jokers needs
+    /// `52 + j` entries. Suited cards with a rank outside 1 to 13 have no
+    /// index and give `None`.
+    pub fn to_index(&self) -> Option<usize> {
+        match *self {
+            Card::Regular(s, n) | Card::Special(s, n) if (1..14).contains(&n) =>
+                Some(13 * s as usize + n as usize - 1),
+            Card::Regular(..) | Card::Special(..) => None,
+            Card::Joker(n) => Some(52 + n as usize),
+        }
+    }
+
+    /// The inverse of `to_index`, with the default special cards.
+    pub fn from_index(i: usize) -> Option<Card> {
+        Card::from_index_with(i, &DEFAULT_SPECIALS)
+    }
+
+    /// Like `from_index`, with `specials` as the special cards.
+    pub fn from_index_with(i: usize, specials: &[(Suit, u8)]) -> Option<Card> {
+        if i < 52 {
+            Some(Card::with_specials(Suit::all()[i / 13], (i % 13) as u8 + 1, specials))
+        } else if i - 52 <= u8::MAX as usize {
+            Some(Card::Joker((i - 52) as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the card could come from a deck under the default rules;
+    /// see `is_valid_with`.
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_with(&DEFAULT_SPECIALS)
+    }
+
+    /// Whether a suited card has a rank from 1 to 13 and is special exactly
+    /// when `specials` lists it. Jokers are always valid.
+    pub fn is_valid_with(&self, specials: &[(Suit, u8)]) -> bool {
+        self.check_with(specials).is_ok()
+    }
+
+    pub(crate) fn check_with(&self, specials: &[(Suit, u8)]) -> Result<(), CardError> {
+        match *self {
+            Card::Regular(s, n) | Card::Special(s, n) => {
+                if Card::try_with_specials(s, n, specials)? == *self {
+                    Ok(())
+                } else {
+                    Err(CardError::SpecialMismatch(*self))
+                }
+            },
+            Card::Joker(_) => Ok(()),
+        }
+    }
+
+    pub fn try_with_specials(suit: Suit, num: u8, specials: &[(Suit, u8)])
+            -> Result<Self, CardError> {
+        if !(1..14).contains(&num) {
+            Err(CardError::RankOutOfRange(num))
+        } else if specials.contains(&(suit, num)) {
+            Ok(Card::Special(suit, num))
+        } else {
+            Ok(Card::Regular(suit, num))
+        }
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Card) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Card {
+    fn cmp(&self, other: &Card) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CardError {
+    RankOutOfRange(u8),
+    /// A regular card that the special cards in play list, or a special
+    /// card that they don't.
+    SpecialMismatch(Card),
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CardError::RankOutOfRange(n) => write!(f, "rank {} is not between 1 and 13", n),
+            CardError::SpecialMismatch(card @ Card::Special(..)) =>
+                write!(f, "{} is marked special but is not one of the special cards", card),
+            CardError::SpecialMismatch(card) =>
+                write!(f, "{} is one of the special cards but not marked special", card),
+        }
+    }
+}
+
+impl error::Error for CardError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseCardError {
+    Empty,
+    UnknownSuit(char),
+    InvalidRank(String),
+    OutOfRange(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseCardError::Empty => write!(f, "empty card string"),
+            ParseCardError::UnknownSuit(c) => write!(f, "unknown suit {:?}", c),
+            ParseCardError::InvalidRank(ref s) => write!(f, "invalid rank {:?}", s),
+            ParseCardError::OutOfRange(ref s) => write!(f, "number {:?} out of range", s),
+        }
+    }
+}
+
+impl error::Error for ParseCardError {}
+
+impl Card {
+    /// Parses the `Display` form of a card like `FromStr`, but cards in
+    /// `specials` parse as `Card::Special` instead of `DEFAULT_SPECIALS`.
+    pub fn parse_with(s: &str, specials: &[(Suit, u8)]) -> Result<Card, ParseCardError> {
+        let mut chars = s.chars();
+        let first = chars.next().ok_or(ParseCardError::Empty)?;
+        let rest = chars.as_str();
+        if first == 'J' {
+            if rest.is_empty() || !rest.chars().all(|c| c.is_ascii_digit()) {
+                return Err(ParseCardError::InvalidRank(s.to_owned()));
+            }
+            return match rest.parse() {
+                Ok(n) => Ok(Card::Joker(n)),
+                Err(_) => Err(ParseCardError::OutOfRange(rest.to_owned())),
+            };
+        }
+        let suit = Suit::from_char(first).ok_or(ParseCardError::UnknownSuit(first))?;
+        let num = match rest {
+            "A" => 1,
+            "K" => 13,
+            "Q" => 12,
+            "J" => 11,
+            "T" => 10,
+            _ if rest.len() == 1 && rest.chars().all(|c| c.is_ascii_digit()) => {
+                let n = rest.parse().unwrap();
+                if !(2..10).contains(&n) {
+                    return Err(ParseCardError::OutOfRange(rest.to_owned()));
+                }
+                n
+            },
+            _ => return Err(ParseCardError::InvalidRank(rest.to_owned())),
+        };
+        Ok(Card::with_specials(suit, num, specials))
+    }
+}
+
+/// Parses the `Display` form of a card, e.g. `♠A`, `♥T`, `♣7` or `J0`.
+/// Cards in `DEFAULT_SPECIALS` parse as `Card::Special`; see `parse_with`.
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Card::parse_with(s, &DEFAULT_SPECIALS)
+    }
+}
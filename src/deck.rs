@@ -0,0 +1,206 @@
+use std::collections::VecDeque;
+use std::collections::vec_deque;
+
+use rand::Rng;
+
+use card::{Card, CardError, Suit};
+use hand::Hand;
+use rules::GameRules;
+
+/// A pile of cards. The back of `cards` is the top of the deck.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deck {
+    cards: VecDeque<Card>,
+}
+
+impl Deck {
+    pub fn empty() -> Deck {
+        Deck {
+            cards: VecDeque::new(),
+        }
+    }
+
+    /// An empty deck with room for `n` cards before it reallocates.
+    pub fn with_capacity(n: usize) -> Deck {
+        Deck {
+            cards: VecDeque::with_capacity(n),
+        }
+    }
+
+    /// A deck holding exactly `cards`; the last card is the first `pop`.
+    /// Fails on the first card that is not valid under the default rules;
+    /// see `Card::is_valid`.
+    pub fn from_cards(cards: Vec<Card>) -> Result<Deck, CardError> {
+        Deck::from_cards_with(cards, &GameRules::default())
+    }
+
+    /// Like `from_cards`, with `rules.specials` as the special cards.
+    pub fn from_cards_with(cards: Vec<Card>, rules: &GameRules) -> Result<Deck, CardError> {
+        for c in cards.iter() {
+            c.check_with(&rules.specials)?;
+        }
+        Ok(Deck::from_cards_unchecked(cards))
+    }
+
+    /// A deck holding exactly `cards`, however malformed.
+    pub fn from_cards_unchecked(cards: Vec<Card>) -> Deck {
+        Deck {
+            cards: cards.into(),
+        }
+    }
+
+    pub fn new(jokers: u8) -> Deck {
+        Deck::with_decks(1, jokers)
+    }
+
+    /// `decks` full 52-card sets followed by `jokers` jokers.
+    pub fn with_decks(decks: usize, jokers: u8) -> Deck {
+        Deck::with_rules(decks, jokers, &GameRules::default())
+    }
+
+    /// Like `with_decks`, with `rules.specials` as the special cards.
+    pub fn with_rules(decks: usize, jokers: u8, rules: &GameRules) -> Deck {
+        let mut res = VecDeque::with_capacity(52 * decks + jokers as usize);
+        for _ in 0..decks {
+            for &suit in Suit::all().iter() {
+                for num in 1..14 {
+                    res.push_back(Card::with_specials(suit, num, &rules.specials));
+                }
+            }
+        }
+        for i in 0..jokers {
+            res.push_back(Card::Joker(i));
+        }
+        Deck {
+            cards: res,
+        }
+    }
+
+    /// The cards from bottom to top, the inverse of `from_cards`.
+    pub fn into_cards(self) -> Vec<Card> {
+        self.cards.into()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// How many cards the deck can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cards.capacity()
+    }
+
+    /// Iterates from the bottom of the deck to the top, so the card `pop`
+    /// would return next comes last.
+    pub fn iter(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter()
+    }
+
+    /// Shuffles the deck; the same as `shuffle_with`.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.shuffle_with(rng);
+    }
+
+    /// A Fisher–Yates shuffle drawing only `next_u32` from `rng`, so that a
+    /// seed gives the same order on every platform and whatever `rand`'s
+    /// own shuffling does in later versions.
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let cards = self.cards.make_contiguous();
+        for i in (1..cards.len()).rev() {
+            cards.swap(i, below(rng, i as u64 + 1) as usize);
+        }
+    }
+
+    /// Places a card on top of the deck.
+    pub fn push(&mut self, card: Card) {
+        self.cards.push_back(card);
+    }
+
+    /// Draws the top card; the same as `draw_top`.
+    pub fn pop(&mut self) -> Option<Card> {
+        self.draw_top()
+    }
+
+    /// Draws the top card, i.e. the one most recently pushed.
+    pub fn draw_top(&mut self) -> Option<Card> {
+        self.cards.pop_back()
+    }
+
+    /// Draws up to `n` cards from the top, top card first. Returns fewer
+    /// cards if the deck runs out.
+    pub fn draw_n(&mut self, n: usize) -> Vec<Card> {
+        let n = n.min(self.cards.len());
+        let mut res = Vec::with_capacity(n);
+        for _ in 0..n {
+            res.push(self.cards.pop_back().unwrap());
+        }
+        res
+    }
+
+    /// Returns the card `pop` would draw next. Unlike `Game`'s draw, this
+    /// never looks at the discard pile: an empty deck peeks as `None` even
+    /// if the next draw would reshuffle the discard pile into it.
+    pub fn peek(&self) -> Option<&Card> {
+        self.cards.back()
+    }
+
+    /// Draws the bottom card, i.e. the one that would be drawn last.
+    pub fn draw_bottom(&mut self) -> Option<Card> {
+        self.cards.pop_front()
+    }
+
+    /// Moves one copy of `card` to the top, returning whether it was found.
+    pub(crate) fn move_to_top(&mut self, card: Card) -> bool {
+        match self.cards.iter().position(|&c| c == card) {
+            Some(i) => {
+                self.cards.remove(i);
+                self.cards.push_back(card);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Puts the deck in the order of a freshly built one.
+    pub(crate) fn sort(&mut self) {
+        self.cards.make_contiguous().sort();
+    }
+
+    /// Moves all cards of `other` on top of this deck.
+    pub(crate) fn append(&mut self, other: &mut Deck) {
+        self.cards.append(&mut other.cards);
+    }
+
+    pub fn take(&mut self, hand: &mut Hand) {
+        for c in hand.cards.iter() {
+            self.cards.push_back(*c);
+        }
+        hand.clear();
+    }
+}
+
+/// A uniform sample from `0..n` for `n <= 2^32`, rejecting the values
+/// that would bias a plain remainder.
+fn below<R: Rng + ?Sized>(rng: &mut R, n: u64) -> u64 {
+    let limit = (1 << 32) / n * n;
+    loop {
+        let x = rng.next_u32() as u64;
+        if x < limit {
+            return x % n;
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Deck {
+    type Item = &'a Card;
+    type IntoIter = vec_deque::Iter<'a, Card>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
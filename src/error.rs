@@ -0,0 +1,25 @@
+use std::error;
+use std::fmt;
+
+/// Misuse of the engine's API that would otherwise panic.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrugelError {
+    /// A strategy was asked to choose among no players.
+    NoEligiblePlayers,
+    /// A hand was asked about a new card after it had already won.
+    WinningHand,
+    /// A strategy picked a player who cannot receive the card.
+    IneligibleChoice(usize),
+}
+
+impl fmt::Display for PrugelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrugelError::NoEligiblePlayers => write!(f, "no player can receive the card"),
+            PrugelError::WinningHand => write!(f, "the hand has already won"),
+            PrugelError::IneligibleChoice(i) => write!(f, "player {} cannot receive the card", i),
+        }
+    }
+}
+
+impl error::Error for PrugelError {}
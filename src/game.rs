@@ -0,0 +1,1181 @@
+use std;
+use std::error;
+use std::fmt;
+use std::iter;
+
+use rand::{Rng, SeedableRng};
+use rand::prng::XorShiftRng;
+
+use card::{Card, ParseCardError};
+use deck::Deck;
+use hand::{Hand, HandSum, WinCondition};
+use observer::GameObserver;
+use rules::GameRules;
+use seed::{fnv1a, seed_bytes, FNV_OFFSET};
+use strategy::Strategy;
+
+pub const ROUNDS_UNTIL_WIN_LIMIT: usize = 10_000;
+
+/// The most games `Game::play_match` plays before giving up.
+pub const MATCH_GAMES_LIMIT: usize = 1_000;
+
+/// The most cards `Game::with_rules` reserves room for in each hand.
+const HAND_CAPACITY: usize = 8;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Game {
+    deck: Deck,
+    discard: Deck,
+    /// Special cards taken out of play by `GameRules::consume_special_on_win`.
+    removed: Deck,
+    players: Vec<Hand>,
+    round: usize,
+    rules: GameRules,
+    history: Vec<RoundResult>,
+    undo_log: Vec<Undo>,
+    wins: Vec<u32>,
+    /// Points from `GameRules::points` for each win.
+    scores: Vec<u32>,
+    /// Consecutive rounds in which nobody received the card.
+    idle_rounds: usize,
+    /// Set when the discard pile was reshuffled into the deck for a card
+    /// the strategy then failed to place, so the next round played reports
+    /// the reshuffle.
+    reshuffle_pending: bool,
+    names: Option<Vec<String>>,
+    /// The number of cards the game was created with, for `validate`.
+    cards: usize,
+    /// Used by the methods that don't take an explicit RNG. This is a
+    /// concrete type rather than a boxed `Rng` so that `Game` stays `Clone`
+    /// and serializable.
+    rng: XorShiftRng,
+    /// Scratch space for `step`; always empty between calls.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    eligible: Vec<usize>,
+}
+
+/// Games compare equal if they have the same cards in the same order, the
+/// same hands, rules, round, history and tallies.
+///
+/// Deck and discard order count, since they decide how the game goes on:
+/// two games holding the same cards in a different order are not equal.
+/// The owned RNG is not compared, so a copy that was given a new RNG with
+/// `set_rng` still equals the original.
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        self.deck == other.deck
+            && self.discard == other.discard
+            && self.removed == other.removed
+            && self.players == other.players
+            && self.round == other.round
+            && self.rules == other.rules
+            && self.history == other.history
+            && self.undo_log == other.undo_log
+            && self.wins == other.wins
+            && self.scores == other.scores
+            && self.idle_rounds == other.idle_rounds
+            && self.reshuffle_pending == other.reshuffle_pending
+            && self.names == other.names
+    }
+}
+
+/// A copy of a game's position made by `Game::to_snapshot`, to be put back
+/// with `Game::restore`. It holds the deck in its exact draw order, the
+/// discard and removed piles, the hands, the round, the rules and the win
+/// and score tallies, but neither the history nor the undo log.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameSnapshot {
+    deck: Deck,
+    discard: Deck,
+    removed: Deck,
+    players: Vec<Hand>,
+    round: usize,
+    rules: GameRules,
+    wins: Vec<u32>,
+    scores: Vec<u32>,
+    idle_rounds: usize,
+    reshuffle_pending: bool,
+    cards: usize,
+}
+
+impl GameSnapshot {
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    pub fn hands(&self) -> &[Hand] {
+        &self.players
+    }
+}
+
+/// What `Game::undo` needs beyond the `RoundResult` to revert a step.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Undo {
+    result: RoundResult,
+    /// Number of cards moved from the receiver's hand to the discard pile on a win.
+    taken: usize,
+    idle_rounds: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoundResult {
+    pub giver: usize,
+    pub receiver: Option<usize>,
+    pub card: Card,
+    pub win: Option<WinCondition>,
+    /// Whether the discard pile was shuffled into the deck to draw `card`.
+    pub reshuffled: bool,
+}
+
+/// Where a card would go if it were drawn this round; see
+/// `Game::preview_step`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceiverPreview {
+    pub giver: usize,
+    /// Whether any player may receive the card, rather than only the giver.
+    pub red: bool,
+    /// The players who could receive the card, sorted by index.
+    pub eligible: Vec<usize>,
+    /// The receiver when only one player is eligible, so that no strategy
+    /// would be asked.
+    pub forced: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DealError {
+    NotEnoughCards { needed: usize, available: usize },
+    Bust { player: usize, card: Card },
+    Win { player: usize, card: Card },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamesError {
+    pub players: usize,
+    pub names: usize,
+}
+
+impl fmt::Display for NamesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "got {} names for {} players", self.names, self.players)
+    }
+}
+
+impl error::Error for NamesError {}
+
+impl fmt::Display for DealError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DealError::NotEnoughCards { needed, available } =>
+                write!(f, "deal needs {} cards but the deck has {}", needed, available),
+            DealError::Bust { player, card } =>
+                write!(f, "dealing {} would bust player {}", card, player),
+            DealError::Win { player, card } =>
+                write!(f, "dealing {} would win for player {}", card, player),
+        }
+    }
+}
+
+impl error::Error for DealError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepError {
+    /// The strategy picked a player outside `eligible`.
+    InvalidStrategyChoice { returned: usize, eligible: Vec<usize> },
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StepError::InvalidStrategyChoice { returned, ref eligible } =>
+                write!(f, "strategy chose player {}, but only {:?} can receive the card",
+                       returned, eligible),
+        }
+    }
+}
+
+impl error::Error for StepError {}
+
+/// Where a transcript passed to `Game::replay` parts from the rules. Each
+/// variant gives the `index` of the offending round in the transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// The game reached a stalemate before the transcript ended.
+    Stalemate { index: usize },
+    Giver { index: usize, recorded: usize, expected: usize },
+    /// The round's reshuffle flag doesn't match whether the deck was empty.
+    Reshuffle { index: usize, recorded: bool },
+    /// The card is not the one on top of the deck or, after a reshuffle,
+    /// not in the deck at all.
+    Card { index: usize, recorded: Card },
+    Receiver { index: usize, recorded: Option<usize>, eligible: Vec<usize> },
+    /// Playing the round gave a different result, e.g. a different win.
+    Outcome { index: usize, recorded: RoundResult, played: RoundResult },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReplayError::Stalemate { index } =>
+                write!(f, "round {}: the game is already a stalemate", index),
+            ReplayError::Giver { index, recorded, expected } =>
+                write!(f, "round {}: player {} gave, but it was player {}'s turn",
+                       index, recorded, expected),
+            ReplayError::Reshuffle { index, recorded: true } =>
+                write!(f, "round {}: reshuffled although the deck was not empty", index),
+            ReplayError::Reshuffle { index, recorded: false } =>
+                write!(f, "round {}: the deck was empty but no reshuffle was recorded", index),
+            ReplayError::Card { index, recorded } =>
+                write!(f, "round {}: {} cannot be drawn", index, recorded),
+            ReplayError::Receiver { index, recorded: Some(i), ref eligible } =>
+                write!(f, "round {}: player {} received, but only {:?} could", index, i, eligible),
+            ReplayError::Receiver { index, recorded: None, ref eligible } =>
+                write!(f, "round {}: nobody received, but {:?} could", index, eligible),
+            ReplayError::Outcome { index, ref recorded, ref played } =>
+                write!(f, "round {}: recorded {} but played {}", index, recorded.to_record(),
+                       played.to_record()),
+        }
+    }
+}
+
+impl error::Error for ReplayError {}
+
+/// A broken invariant found by `Game::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvariantError {
+    /// The deck, discard pile, removed pile and hands together hold
+    /// `found` cards instead of the `expected` the game started with.
+    CardCount { expected: usize, found: usize },
+    /// A hand sums to more than `GameRules::target_sum`.
+    Bust { player: usize, sum: u8 },
+    /// A hand meets a win condition; winning hands are emptied at once.
+    WinningHand { player: usize, condition: WinCondition },
+    /// Fewer rounds were played than the history or undo log records.
+    Round { round: usize, recorded: usize },
+    /// The win or score tallies don't have one entry per player.
+    Tallies { players: usize, wins: usize, scores: usize },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvariantError::CardCount { expected, found } =>
+                write!(f, "the game holds {} cards instead of {}", found, expected),
+            InvariantError::Bust { player, sum } =>
+                write!(f, "player {} has a bust hand summing to {}", player, sum),
+            InvariantError::WinningHand { player, condition } =>
+                write!(f, "player {} still holds a hand winning by {:?}", player, condition),
+            InvariantError::Round { round, recorded } =>
+                write!(f, "round is {} but {} rounds are recorded", round, recorded),
+            InvariantError::Tallies { players, wins, scores } =>
+                write!(f, "{} players but {} win counts and {} scores", players, wins, scores),
+        }
+    }
+}
+
+impl error::Error for InvariantError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StepOutcome {
+    Played(RoundResult),
+    /// Both the deck and the discard pile are empty.
+    Exhausted,
+    /// Nobody has received a card for `GameRules::stalemate_rounds` rounds,
+    /// so no round was played.
+    Stalemate,
+}
+
+impl StepOutcome {
+    pub fn played(self) -> Option<RoundResult> {
+        match self {
+            StepOutcome::Played(result) => Some(result),
+            StepOutcome::Exhausted | StepOutcome::Stalemate => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseRecordError {
+    FieldCount(usize),
+    InvalidReshuffle(String),
+    InvalidPlayer(String),
+    InvalidCard(ParseCardError),
+    InvalidWin(String),
+}
+
+impl fmt::Display for ParseRecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseRecordError::FieldCount(n) => write!(f, "expected 4 or 5 fields, got {}", n),
+            ParseRecordError::InvalidReshuffle(ref s) => write!(f, "invalid reshuffle flag {:?}", s),
+            ParseRecordError::InvalidPlayer(ref s) => write!(f, "invalid player {:?}", s),
+            ParseRecordError::InvalidCard(ref e) => write!(f, "invalid card: {}", e),
+            ParseRecordError::InvalidWin(ref s) => write!(f, "invalid win condition {:?}", s),
+        }
+    }
+}
+
+impl error::Error for ParseRecordError {}
+
+impl RoundResult {
+    /// A line of the form `giver,card,receiver,win,reshuffled`, with the card
+    /// in its `Display` form, the win condition by name (e.g. `FiveCards`),
+    /// `-` for no receiver or no win, and `R` or `-` for whether the deck was
+    /// reshuffled. The delimiter is always a comma.
+    pub fn to_record(&self) -> String {
+        let receiver = match self.receiver {
+            Some(r) => r.to_string(),
+            None => "-".to_owned(),
+        };
+        let win = match self.win {
+            Some(w) => format!("{:?}", w),
+            None => "-".to_owned(),
+        };
+        let reshuffled = if self.reshuffled { "R" } else { "-" };
+        format!("{},{},{},{},{}", self.giver, self.card, receiver, win, reshuffled)
+    }
+
+    /// Parses a line produced by `to_record`. The reshuffle field may be
+    /// left out, as in records written before it existed. Cards parse with
+    /// the default specials; see `from_record_with`.
+    pub fn from_record(line: &str) -> Result<RoundResult, ParseRecordError> {
+        RoundResult::from_record_with(line, &GameRules::default())
+    }
+
+    /// Parses a line produced by `to_record` in a game played under
+    /// `rules`, so its special cards come back as `Card::Special`.
+    pub fn from_record_with(line: &str, rules: &GameRules)
+            -> Result<RoundResult, ParseRecordError> {
+        let fields: Vec<&str> = line.trim_end_matches(&['\r', '\n'][..]).split(',').collect();
+        if fields.len() != 4 && fields.len() != 5 {
+            return Err(ParseRecordError::FieldCount(fields.len()));
+        }
+        let player = |s: &str| s.parse().map_err(|_| ParseRecordError::InvalidPlayer(s.to_owned()));
+        let giver = player(fields[0])?;
+        let card = Card::parse_with(fields[1], &rules.specials)
+            .map_err(ParseRecordError::InvalidCard)?;
+        let receiver = match fields[2] {
+            "-" => None,
+            s => Some(player(s)?),
+        };
+        let win = match fields[3] {
+            "-" => None,
+            "FiveCards" => Some(WinCondition::FiveCards),
+            "TwentyFive" => Some(WinCondition::TwentyFive),
+            "Special" => Some(WinCondition::Special),
+            "Joker" => Some(WinCondition::Joker),
+            s => return Err(ParseRecordError::InvalidWin(s.to_owned())),
+        };
+        let reshuffled = match fields.get(4) {
+            None | Some(&"-") => false,
+            Some(&"R") => true,
+            Some(s) => return Err(ParseRecordError::InvalidReshuffle(s.to_string())),
+        };
+        Ok(RoundResult {
+            giver,
+            receiver,
+            card,
+            win,
+            reshuffled,
+        })
+    }
+
+    /// Describes the round, naming players by `Game::player_name`.
+    pub fn describe(&self, g: &Game) -> String {
+        let giver = g.player_name(self.giver);
+        let sum = |r: usize| g.players.get(r).and_then(|h| h.hand_sum_with(&g.rules).as_no_win());
+        match (self.receiver, self.win) {
+            (Some(r), None) => match sum(r) {
+                Some(sum) =>
+                    format!("{} {} to {} => {}", giver, self.card, g.player_name(r), sum),
+                None =>
+                    format!("{} {} to {}", giver, self.card, g.player_name(r)),
+            },
+            (Some(r), Some(w)) =>
+                format!("{} {} to {} => {:?}",
+                        giver, self.card, g.player_name(r), w),
+            (None, _) =>
+                format!("{} {} to nobody", giver, self.card),
+        }
+    }
+}
+
+impl Game {
+    pub fn new(players: usize, jokers: u8) -> Self {
+        Game::with_rules(players, 1, jokers, GameRules::default())
+    }
+
+    pub fn with_rules(players: usize, decks: usize, jokers: u8, rules: GameRules) -> Self {
+        // Cloning an empty Vec drops its capacity, so build each hand anew.
+        // `cards_to_win` may be huge to switch off five-card wins, so only
+        // reserve room for a typical hand.
+        let capacity = rules.cards_to_win.min(HAND_CAPACITY);
+        let hands = (0..players).map(|_| Hand::with_capacity(capacity)).collect();
+        let deck = Deck::with_rules(decks, jokers, &rules);
+        let cards = deck.len();
+        Game {
+            deck,
+            discard: Deck::with_capacity(cards),
+            removed: Deck::empty(),
+            players: hands,
+            round: 0,
+            rules,
+            history: Vec::new(),
+            undo_log: Vec::new(),
+            wins: vec![0; players],
+            scores: vec![0; players],
+            idle_rounds: 0,
+            reshuffle_pending: false,
+            names: None,
+            cards,
+            rng: XorShiftRng::from_seed(seed_bytes(0)),
+            eligible: Vec::with_capacity(players),
+        }
+    }
+
+    /// Names the players for `RoundResult::describe`. There must be exactly
+    /// one name per player.
+    pub fn set_names(&mut self, names: Vec<String>) -> Result<(), NamesError> {
+        if names.len() != self.players.len() {
+            return Err(NamesError { players: self.players.len(), names: names.len() });
+        }
+        self.names = Some(names);
+        Ok(())
+    }
+
+    pub fn names(&self) -> Option<&[String]> {
+        self.names.as_ref().map(|n| &n[..])
+    }
+
+    /// The player's name if names are set, and otherwise their index.
+    pub fn player_name(&self, i: usize) -> String {
+        match self.names.as_ref().and_then(|names| names.get(i)) {
+            Some(name) => name.clone(),
+            None => i.to_string(),
+        }
+    }
+
+    pub fn num_players(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn hand(&self, i: usize) -> Option<&Hand> {
+        self.players.get(i)
+    }
+
+    pub fn hands(&self) -> &[Hand] {
+        &self.players
+    }
+
+    /// The number of rounds played so far.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+
+    /// The player who gives the next card drawn.
+    pub fn current_giver(&self) -> usize {
+        self.round % self.players.len()
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    pub fn wins(&self) -> &[u32] {
+        &self.wins
+    }
+
+    pub fn scores(&self) -> &[u32] {
+        &self.scores
+    }
+
+    /// Renders every hand with its sum, plus the deck and discard sizes.
+    /// The player whose turn it is is marked with `>`. With `color`, red
+    /// cards are wrapped in ANSI color codes.
+    pub fn render_board(&self, color: bool) -> String {
+        let giver = self.current_giver();
+        let mut res = format!("Round {}, deck {}, discard {}\n",
+                              self.round, self.deck.len(), self.discard.len());
+        for (i, hand) in self.players.iter().enumerate() {
+            res.push_str(if i == giver { "> " } else { "  " });
+            res.push_str(&format!("{}: ", i));
+            for c in hand.cards.iter() {
+                res.push_str(&c.render(color));
+                res.push(' ');
+            }
+            match hand.hand_sum_with(&self.rules) {
+                HandSum::Win(w) => res.push_str(&format!("=> {:?}\n", w)),
+                HandSum::NoWin(sum) => res.push_str(&format!("=> {}\n", sum)),
+            }
+        }
+        res
+    }
+
+    pub fn player_sums(&self) -> Vec<HandSum> {
+        self.players.iter().map(|h| h.hand_sum_with(&self.rules)).collect()
+    }
+
+    /// The cards still in play but in no player's hand, i.e. the deck and
+    /// the discard pile together, sorted as by `Ord for Card`. Special cards
+    /// consumed by a win are not included.
+    pub fn remaining_cards(&self) -> Vec<Card> {
+        let mut res: Vec<Card> = self.deck.iter().chain(self.discard.iter()).cloned().collect();
+        res.sort();
+        res
+    }
+
+    /// Counts `remaining_cards` by rank: index `n` holds the regular cards
+    /// of rank `n` from 1 (ace) to 13 (king), and index 0 holds the jokers.
+    /// Special cards have no rank and are not counted, nor are malformed
+    /// cards with a rank outside 1 to 13. Counts stop at 255, which only
+    /// matters with more than 63 decks.
+    pub fn remaining_by_rank(&self) -> [u8; 14] {
+        let mut res = [0u8; 14];
+        for c in self.deck.iter().chain(self.discard.iter()) {
+            let i = match *c {
+                Card::Regular(_, n @ 1..=13) => n as usize,
+                Card::Joker(_) => 0,
+                Card::Regular(..) | Card::Special(..) => continue,
+            };
+            res[i] = res[i].saturating_add(1);
+        }
+        res
+    }
+
+    /// Counts `remaining_cards` by suit, indexed in the order of
+    /// `Suit::all`. Special cards count towards their suit; jokers have no
+    /// suit and are not counted. Counts stop at 255, which a suit reaches
+    /// with 20 decks.
+    pub fn remaining_by_suit(&self) -> [u8; 4] {
+        let mut res = [0u8; 4];
+        for s in self.deck.iter().chain(self.discard.iter()).filter_map(Card::suit) {
+            res[s as usize] = res[s as usize].saturating_add(1);
+        }
+        res
+    }
+
+    /// Checks that no card was lost or duplicated, that every hand is under
+    /// the target sum and not winning, that `round` covers the history and
+    /// undo log, and that there is a win and score tally per player.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let found = self.deck.len() + self.discard.len() + self.removed.len()
+            + self.players.iter().map(Hand::len).sum::<usize>();
+        if found != self.cards {
+            return Err(InvariantError::CardCount { expected: self.cards, found });
+        }
+        for (player, hand) in self.players.iter().enumerate() {
+            match hand.hand_sum_with(&self.rules) {
+                HandSum::Win(condition) =>
+                    return Err(InvariantError::WinningHand { player, condition }),
+                HandSum::NoWin(sum) if sum > self.rules.target_sum =>
+                    return Err(InvariantError::Bust { player, sum }),
+                HandSum::NoWin(_) => (),
+            }
+        }
+        let recorded = self.history.len().max(self.undo_log.len());
+        if recorded > self.round {
+            return Err(InvariantError::Round { round: self.round, recorded });
+        }
+        let players = self.players.len();
+        if self.wins.len() != players || self.scores.len() != players {
+            return Err(InvariantError::Tallies {
+                players,
+                wins: self.wins.len(),
+                scores: self.scores.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// A hash of the position as the players can see it, for transposition
+    /// tables. It covers the round (and with it the giver), each player's
+    /// hand as a set of cards, and the contents of the discard pile and of
+    /// the cards removed from play. The
+    /// order of the deck is hidden and left out; its contents follow from
+    /// the rest. Rules, history, win tallies, names and the stalemate count
+    /// are not included either. The hash is FNV-1a and does not change
+    /// between runs or platforms.
+    pub fn position_key(&self) -> u64 {
+        fn hash_cards(hash: u64, mut cards: Vec<Card>) -> u64 {
+            cards.sort();
+            let mut hash = fnv1a(hash, &(cards.len() as u64).to_le_bytes());
+            for c in cards {
+                let bytes = match c {
+                    Card::Regular(s, n) => [0, s as u8, n],
+                    Card::Special(s, n) => [1, s as u8, n],
+                    Card::Joker(n) => [2, 0, n],
+                };
+                hash = fnv1a(hash, &bytes);
+            }
+            hash
+        }
+        let mut hash = fnv1a(FNV_OFFSET, &(self.round as u64).to_le_bytes());
+        hash = fnv1a(hash, &(self.players.len() as u64).to_le_bytes());
+        for hand in self.players.iter() {
+            hash = hash_cards(hash, hand.cards.to_vec());
+        }
+        hash = hash_cards(hash, self.discard.iter().cloned().collect());
+        hash_cards(hash, self.removed.iter().cloned().collect())
+    }
+
+    /// How many cards the deck can hold without reallocating. The deck
+    /// itself is not exposed, since its order is hidden from the players.
+    pub fn deck_capacity(&self) -> usize {
+        self.deck.capacity()
+    }
+
+    /// The discard pile, whose top card is the one most recently discarded.
+    pub fn discard(&self) -> &Deck {
+        &self.discard
+    }
+
+    /// Special cards that won and left play under
+    /// `GameRules::consume_special_on_win`. They return only on `reset`.
+    pub fn removed(&self) -> &Deck {
+        &self.removed
+    }
+
+    /// The fraction of cards that could be drawn next which would let
+    /// `player` win this round, assuming a red card goes to `player` when
+    /// it may. Black cards, special cards and, unless
+    /// `GameRules::joker_is_red` is set, jokers only reach the giver.
+    ///
+    /// While the deck holds cards, only the deck is drawn from. Once it is
+    /// empty the discard pile is assumed to be reshuffled into it, so every
+    /// discarded card is equally likely. Returns 0 if there is nothing left
+    /// to draw.
+    pub fn win_probability(&self, player: usize) -> f64 {
+        let pool = if self.deck.is_empty() { &self.discard } else { &self.deck };
+        if pool.is_empty() {
+            return 0.0;
+        }
+        let giver = self.current_giver();
+        let hand = &self.players[player];
+        let wins = pool.iter().filter(|&&card| {
+            if !(self.is_red(card) || player == giver) || !hand.can_accept_with(card, &self.rules) {
+                return false;
+            }
+            let mut after = hand.clone();
+            after.push(card);
+            matches!(after.hand_sum_with(&self.rules), HandSum::Win(_))
+        }).count();
+        wins as f64 / pool.len() as f64
+    }
+
+    pub fn history(&self) -> &[RoundResult] {
+        &self.history
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Replaces the RNG used by `shuffle`, `reset` and `step`. A new game
+    /// starts out with a fixed seed.
+    pub fn set_rng(&mut self, rng: XorShiftRng) {
+        self.rng = rng;
+    }
+
+    /// A copy of the game for simulating ahead, with its own RNG seeded
+    /// from `seed` so that rollouts from the same position diverge. The
+    /// deck keeps its order; call `shuffle` on the copy to sample unknown
+    /// cards. The copy's history is cleared, since rollouts rarely need it.
+    pub fn clone_for_rollout(&self, seed: u64) -> Game {
+        let mut game = self.clone();
+        game.history = Vec::new();
+        game.rng = XorShiftRng::from_seed(seed_bytes(seed));
+        game
+    }
+
+    /// Copies the position, deck order included, without the history.
+    pub fn to_snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            deck: self.deck.clone(),
+            discard: self.discard.clone(),
+            removed: self.removed.clone(),
+            players: self.players.clone(),
+            round: self.round,
+            rules: self.rules.clone(),
+            wins: self.wins.clone(),
+            scores: self.scores.clone(),
+            idle_rounds: self.idle_rounds,
+            reshuffle_pending: self.reshuffle_pending,
+            cards: self.cards,
+        }
+    }
+
+    /// Puts back a position from `to_snapshot`. The history and undo log
+    /// are cleared since they no longer lead up to the position. The RNG
+    /// is kept, and so are the names unless the number of players changed.
+    pub fn restore(&mut self, snap: GameSnapshot) {
+        if snap.players.len() != self.players.len() {
+            self.names = None;
+        }
+        self.deck = snap.deck;
+        self.discard = snap.discard;
+        self.removed = snap.removed;
+        self.players = snap.players;
+        self.round = snap.round;
+        self.rules = snap.rules;
+        self.wins = snap.wins;
+        self.scores = snap.scores;
+        self.idle_rounds = snap.idle_rounds;
+        self.reshuffle_pending = snap.reshuffle_pending;
+        self.cards = snap.cards;
+        self.history.clear();
+        self.undo_log.clear();
+    }
+
+    pub fn shuffle(&mut self) {
+        self.deck.shuffle(&mut self.rng);
+    }
+
+    pub fn shuffle_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.deck.shuffle(rng);
+    }
+
+    /// Deals `per_player` cards from the top of the deck to each hand in
+    /// turn, starting with player 0.
+    ///
+    /// Wins only happen during play, so a deal in which some card would bust
+    /// a hand or put it in a winning state (including any joker or special
+    /// card) is rejected. On error the game is left untouched, so the caller
+    /// can reshuffle and try again. A successful deal cannot be undone.
+    pub fn deal(&mut self, per_player: usize) -> Result<(), DealError> {
+        let needed = per_player * self.players.len();
+        if needed > self.deck.len() {
+            return Err(DealError::NotEnoughCards { needed, available: self.deck.len() });
+        }
+        // Check the deal on copies of the hands first, then deal into the
+        // real ones so they keep their reserved capacity.
+        let mut hands = self.players.clone();
+        let dealt: Vec<Card> = self.deck.iter().skip(self.deck.len() - needed).cloned().collect();
+        for (k, &card) in dealt.iter().rev().enumerate() {
+            let player = k % hands.len();
+            if !hands[player].can_accept_with(card, &self.rules) {
+                return Err(DealError::Bust { player, card });
+            }
+            hands[player].push(card);
+            if let HandSum::Win(_) = hands[player].hand_sum_with(&self.rules) {
+                return Err(DealError::Win { player, card });
+            }
+        }
+        for k in 0..needed {
+            let card = self.deck.pop().expect("deal: deck was just checked");
+            let player = k % self.players.len();
+            self.players[player].push(card);
+        }
+        self.undo_log.clear();
+        self.idle_rounds = 0;
+        Ok(())
+    }
+
+    /// Gathers every card into a fresh deck and shuffles it, starting a new
+    /// game with the same players, rules and names. Existing allocations are
+    /// reused.
+    ///
+    /// This continues with the game's own RNG rather than re-seeding it, so
+    /// successive games differ; call `set_rng` first to replay a seed.
+    pub fn reset(&mut self) {
+        let mut rng = self.rng.clone();
+        self.reset_with_rng(&mut rng);
+        self.rng = rng;
+    }
+
+    pub fn reset_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.redeal(rng);
+        for w in self.wins.iter_mut() {
+            *w = 0;
+        }
+        for s in self.scores.iter_mut() {
+            *s = 0;
+        }
+    }
+
+    /// Like `reset_with_rng`, but keeps the win and score tallies.
+    fn redeal<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for hand in self.players.iter_mut() {
+            self.deck.take(hand);
+        }
+        self.deck.append(&mut self.discard);
+        self.deck.append(&mut self.removed);
+        self.deck.sort();
+        self.deck.shuffle(rng);
+        self.round = 0;
+        self.history.clear();
+        self.undo_log.clear();
+        self.idle_rounds = 0;
+        self.reshuffle_pending = false;
+    }
+
+    /// Returns the drawn card and whether the discard pile was reshuffled into the deck.
+    fn pop_deck<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<(Card, bool)> {
+        match self.deck.pop() {
+            Some(c) => Some((c, false)),
+            None if self.discard.is_empty() => None,
+            None => {
+                std::mem::swap(&mut self.deck, &mut self.discard);
+                self.deck.shuffle(rng);
+                self.deck.pop().map(|c| (c, true))
+            }
+        }
+    }
+
+    /// The players who may receive `card` from the current giver, sorted by
+    /// index: everyone who can accept a red card, and otherwise just the
+    /// giver if they can accept it. This is the list `step` hands to the
+    /// strategy.
+    pub fn legal_receivers(&self, card: Card) -> Vec<usize> {
+        let mut res = Vec::new();
+        let red = self.is_red(card);
+        push_eligible(&self.players, &self.rules, self.current_giver(), card, red, &mut res);
+        res
+    }
+
+    /// Reports who could receive `card` if it were drawn now, without
+    /// drawing it or asking a strategy.
+    pub fn preview_step(&self, card: Card) -> ReceiverPreview {
+        let giver = self.current_giver();
+        let red = self.is_red(card);
+        let eligible = self.legal_receivers(card);
+        let forced = if eligible.len() == 1 { Some(eligible[0]) } else { None };
+        ReceiverPreview {
+            giver,
+            red,
+            eligible,
+            forced,
+        }
+    }
+
+    /// Whether `card` may go to any player rather than only the giver.
+    fn is_red(&self, card: Card) -> bool {
+        card.is_red() || (card.is_joker() && self.rules.joker_is_red)
+    }
+
+    /// Whether nobody has received a card in the last
+    /// `GameRules::stalemate_rounds` rounds.
+    pub fn is_stalemate(&self) -> bool {
+        self.rules.stalemate_rounds.is_some_and(|n| self.idle_rounds >= n)
+    }
+
+    /// Plays one round, drawing with the game's own RNG.
+    pub fn step<S: Strategy>(&mut self, strategy: &mut S) -> Result<StepOutcome, StepError> {
+        let mut rng = self.rng.clone();
+        let outcome = self.step_with_rng(&mut rng, strategy);
+        self.rng = rng;
+        outcome
+    }
+
+    pub fn step_with_rng<R: Rng + ?Sized, S: Strategy>(&mut self, rng: &mut R, strategy: &mut S)
+            -> Result<StepOutcome, StepError> {
+        self.step_observed(rng, strategy, &mut ())
+    }
+
+    /// Like `step`, reporting each event of the round to `observer`.
+    ///
+    /// If the strategy picks a player who cannot receive the card, the card
+    /// goes back on top of the deck and no round is played, so `observer`
+    /// hears of no draw. A reshuffle that drew the card still stands,
+    /// though, and earlier rounds can no longer be undone; it is reported,
+    /// and recorded in the `RoundResult`, with the next round played.
+    pub fn step_observed<R: Rng + ?Sized, S: Strategy, O: GameObserver>(
+            &mut self, rng: &mut R, strategy: &mut S, observer: &mut O)
+            -> Result<StepOutcome, StepError> {
+        if self.is_stalemate() {
+            return Ok(StepOutcome::Stalemate);
+        }
+        let (card, reshuffled) = match self.pop_deck(rng) {
+            Some(c) => c,
+            None => return Ok(StepOutcome::Exhausted),
+        };
+        self.play_card(card, reshuffled, strategy, observer)
+    }
+
+    /// The rest of `step_observed` once `card` has been drawn.
+    fn play_card<S: Strategy, O: GameObserver>(&mut self, card: Card, reshuffled: bool,
+                                               strategy: &mut S, observer: &mut O)
+            -> Result<StepOutcome, StepError> {
+        let reshuffled = reshuffled || self.reshuffle_pending;
+        let giver = self.current_giver();
+        trace!("round {}: player {} draws {}", self.round, giver, card);
+        self.eligible.clear();
+        let red = self.is_red(card);
+        push_eligible(&self.players, &self.rules, giver, card, red, &mut self.eligible);
+        trace!("round {}: eligible receivers {:?}", self.round, self.eligible);
+        let receiver = match self.eligible.len() {
+            0 => None,
+            1 => Some(self.eligible[0]),
+            _ => {
+                let j = strategy.choose(giver, &self.players, card, &self.eligible);
+                if !self.eligible.contains(&j) {
+                    self.deck.push(card);
+                    if reshuffled {
+                        self.reshuffle_pending = true;
+                        self.undo_log.clear();
+                    }
+                    let eligible = std::mem::take(&mut self.eligible);
+                    return Err(StepError::InvalidStrategyChoice { returned: j, eligible });
+                }
+                Some(j)
+            },
+        };
+        self.eligible.clear();
+        self.reshuffle_pending = false;
+        if reshuffled {
+            observer.on_reshuffle();
+        }
+        observer.on_draw(card);
+        if let Some(i) = receiver {
+            debug!("round {}: player {} gives {} to player {}", self.round, giver, card, i);
+            observer.on_accept(i, card);
+        } else {
+            debug!("round {}: nobody can take {}, discarding", self.round, card);
+        }
+        let mut win = None;
+        let mut taken = 0;
+        match receiver {
+            // A special card wins on its own without joining the hand, unless
+            // the rules leave special wins out.
+            Some(_) if card.is_special()
+                    && self.rules.win_precedence.contains(&WinCondition::Special) => {
+                win = Some(WinCondition::Special);
+                if self.rules.consume_special_on_win {
+                    self.removed.push(card);
+                } else {
+                    self.discard.push(card);
+                }
+            },
+            Some(i) => {
+                self.players[i].accept_with(card, &self.rules);
+                if let HandSum::Win(cond) = self.players[i].hand_sum_with(&self.rules) {
+                    taken = self.players[i].len();
+                    self.discard.take(&mut self.players[i]);
+                    win = Some(cond);
+                }
+            },
+            None => self.discard.push(card),
+        };
+        if let (Some(i), Some(w)) = (receiver, win) {
+            self.wins[i] += 1;
+            self.scores[i] += self.rules.points(w);
+            debug!("round {}: player {} wins by {:?}", self.round, i, w);
+            observer.on_win(i, w);
+        }
+        let idle_rounds = self.idle_rounds;
+        self.idle_rounds = if receiver.is_some() { 0 } else { idle_rounds + 1 };
+        self.round += 1;
+        let result = RoundResult {
+            giver,
+            receiver,
+            card,
+            win,
+            reshuffled,
+        };
+        self.history.push(result.clone());
+        if reshuffled {
+            self.undo_log.clear();
+        } else {
+            self.undo_log.push(Undo {
+                result: result.clone(),
+                taken,
+                idle_rounds,
+            });
+        }
+        Ok(StepOutcome::Played(result))
+    }
+
+    /// Steps up to `n` times, stopping early if the deck runs out or the
+    /// game reaches a stalemate.
+    pub fn run<R: Rng + ?Sized, S: Strategy>(&mut self, rng: &mut R, strategy: &mut S, n: usize)
+            -> Result<Vec<RoundResult>, StepError> {
+        let mut results = Vec::new();
+        for _ in 0..n {
+            match self.step_with_rng(rng, strategy)? {
+                StepOutcome::Played(result) => results.push(result),
+                StepOutcome::Exhausted | StepOutcome::Stalemate => break,
+            }
+        }
+        Ok(results)
+    }
+
+    /// Yields the result of each round, ending once the deck runs out or
+    /// the game reaches a stalemate. If the strategy makes an invalid choice
+    /// the error is yielded and the iterator ends, as `run` would return it.
+    pub fn rounds<'a, R: Rng + ?Sized, S: Strategy>(&'a mut self, rng: &'a mut R,
+                                                    strategy: &'a mut S)
+            -> impl Iterator<Item = Result<RoundResult, StepError>> + 'a {
+        let mut failed = false;
+        iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            match self.step_with_rng(rng, strategy) {
+                Ok(outcome) => outcome.played().map(Ok),
+                Err(e) => {
+                    failed = true;
+                    Some(Err(e))
+                },
+            }
+        })
+    }
+
+    /// Steps until some player wins, returning the winner and condition.
+    /// Gives up and returns `None` if the deck runs out, the game reaches a
+    /// stalemate, or nobody has won after `ROUNDS_UNTIL_WIN_LIMIT` rounds.
+    pub fn run_until_win<R: Rng + ?Sized, S: Strategy>(&mut self, rng: &mut R, strategy: &mut S)
+            -> Result<Option<(usize, WinCondition)>, StepError> {
+        for _ in 0..ROUNDS_UNTIL_WIN_LIMIT {
+            let result = match self.step_with_rng(rng, strategy)?.played() {
+                Some(result) => result,
+                None => return Ok(None),
+            };
+            if let (Some(i), Some(w)) = (result.receiver, result.win) {
+                return Ok(Some((i, w)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Plays back `results`, as recorded by `history`, from `initial`,
+    /// checking every round against the rules. No RNG is involved.
+    ///
+    /// Until the transcript reshuffles, each card must be the one on top of
+    /// the deck. A reshuffle's order is not recorded, so after one any card
+    /// left in the deck may come next, and the deck order of the returned
+    /// game is arbitrary from then on; everything else about the game is
+    /// as it was when the transcript ended.
+    pub fn replay(initial: Game, results: &[RoundResult]) -> Result<Game, ReplayError> {
+        let mut game = initial;
+        let mut ordered = true;
+        for (index, recorded) in results.iter().enumerate() {
+            if game.is_stalemate() {
+                return Err(ReplayError::Stalemate { index });
+            }
+            let expected = game.current_giver();
+            if recorded.giver != expected {
+                return Err(ReplayError::Giver { index, recorded: recorded.giver, expected });
+            }
+            if recorded.reshuffled != (game.deck.is_empty() || game.reshuffle_pending) {
+                return Err(ReplayError::Reshuffle { index, recorded: recorded.reshuffled });
+            }
+            if game.deck.is_empty() {
+                std::mem::swap(&mut game.deck, &mut game.discard);
+                ordered = false;
+            }
+            let drawable = if ordered {
+                game.deck.peek() == Some(&recorded.card)
+            } else {
+                game.deck.move_to_top(recorded.card)
+            };
+            if !drawable {
+                return Err(ReplayError::Card { index, recorded: recorded.card });
+            }
+            let card = game.deck.pop().expect("replay: card was just checked");
+            let eligible = game.preview_step(card).eligible;
+            let legal = match recorded.receiver {
+                Some(i) => eligible.contains(&i),
+                None => eligible.is_empty(),
+            };
+            if !legal {
+                return Err(ReplayError::Receiver { index, recorded: recorded.receiver, eligible });
+            }
+            let mut strategy = Recorded(recorded.receiver.unwrap_or(0));
+            let played = game.play_card(card, recorded.reshuffled, &mut strategy, &mut ())
+                .expect("replay: receiver was just checked")
+                .played()
+                .expect("replay: a drawn card is always played");
+            if played != *recorded {
+                return Err(ReplayError::Outcome { index, recorded: recorded.clone(), played });
+            }
+        }
+        Ok(game)
+    }
+
+    /// Plays games until a player's score reaches `target_score`, returning
+    /// that player. Scores and wins carry over from game to game, and after
+    /// each win the cards are gathered and reshuffled as by `reset`. A game
+    /// that ends without a winner is redealt too. Gives up and returns
+    /// `None` after `MATCH_GAMES_LIMIT` games.
+    pub fn play_match<R: Rng + ?Sized, S: Strategy>(&mut self, rng: &mut R, strategy: &mut S,
+                                                   target_score: u32)
+            -> Result<Option<usize>, StepError> {
+        for _ in 0..MATCH_GAMES_LIMIT {
+            if let Some((i, _)) = self.run_until_win(rng, strategy)? {
+                if self.scores[i] >= target_score {
+                    return Ok(Some(i));
+                }
+            }
+            self.redeal(rng);
+        }
+        Ok(None)
+    }
+
+    /// Reverts the most recent `step`, returning its result.
+    ///
+    /// Undoing a step that reshuffled the discard pile into the deck is not
+    /// supported, since the discard order is lost: such a step cannot be
+    /// undone, and neither can any step before it.
+    pub fn undo(&mut self) -> Option<RoundResult> {
+        let Undo { result, taken, idle_rounds } = self.undo_log.pop()?;
+        let card = match (result.receiver, result.card) {
+            (Some(i), _) if taken > 0 => {
+                // The winning hand was emptied onto the discard pile in order.
+                let card = self.discard.pop().expect("undo: discard pile out of sync");
+                let mut cards = Vec::with_capacity(taken - 1);
+                for _ in 1..taken {
+                    cards.push(self.discard.pop().expect("undo: discard pile out of sync"));
+                }
+                for &c in cards.iter().rev() {
+                    self.players[i].push(c);
+                }
+                card
+            },
+            (Some(_), Card::Special(..)) if result.win == Some(WinCondition::Special)
+                    && self.rules.consume_special_on_win =>
+                self.removed.pop().expect("undo: removed pile out of sync"),
+            (Some(_), Card::Special(..)) if result.win == Some(WinCondition::Special) =>
+                self.discard.pop().expect("undo: discard pile out of sync"),
+            (None, _) => self.discard.pop().expect("undo: discard pile out of sync"),
+            (Some(i), _) => self.players[i].pop().expect("undo: hand out of sync"),
+        };
+        debug_assert_eq!(card, result.card);
+        self.deck.push(card);
+        if let (Some(i), Some(w)) = (result.receiver, result.win) {
+            self.wins[i] -= 1;
+            self.scores[i] -= self.rules.points(w);
+        }
+        self.idle_rounds = idle_rounds;
+        self.round -= 1;
+        if !self.history.is_empty() {
+            self.history.pop();
+        }
+        Some(result)
+    }
+}
+
+/// Appends the players who may receive `card` from `giver` to `out`.
+fn push_eligible(players: &[Hand], rules: &GameRules, giver: usize, card: Card, red: bool,
+                 out: &mut Vec<usize>) {
+    if red {
+        for (i, hand) in players.iter().enumerate() {
+            if hand.can_accept_with(card, rules) {
+                out.push(i);
+            }
+        }
+    } else if players[giver].can_accept_with(card, rules) {
+        out.push(giver);
+    }
+}
+
+/// Picks the receiver named by a transcript; see `Game::replay`.
+struct Recorded(usize);
+
+impl Strategy for Recorded {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, _eligible: &[usize])
+            -> usize {
+        self.0
+    }
+}
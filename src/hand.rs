@@ -0,0 +1,357 @@
+use std::error;
+use std::fmt;
+
+use card::Card;
+use error::PrugelError;
+use rules::GameRules;
+
+/// The storage behind a hand. With the `smallvec` feature, hands of up to
+/// eight cards live inline instead of on the heap.
+#[cfg(feature = "smallvec")]
+pub(crate) type Cards = ::smallvec::SmallVec<[Card; 8]>;
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type Cards = Vec<Card>;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hand {
+    pub(crate) cards: Cards,
+    // Cached from `cards` so that `hand_sum` needn't rescan the hand. The
+    // counts saturate, so an unchecked hand summing past 255 reads as 255.
+    sum: u8,
+    aces: u8,
+    jokers: u8,
+    specials: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WinCondition {
+    /// The hand holds `GameRules::cards_to_win` cards, five by default.
+    FiveCards,
+    TwentyFive,
+    Special,
+    Joker,
+}
+
+impl WinCondition {
+    /// The default score for a win: 1 for five cards, 2 for the target sum
+    /// or a special card, and 3 for a joker. `GameRules::points` can
+    /// override these.
+    pub fn points(&self) -> u32 {
+        match *self {
+            WinCondition::FiveCards => 1,
+            WinCondition::TwentyFive | WinCondition::Special => 2,
+            WinCondition::Joker => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HandSum {
+    Win(WinCondition),
+    NoWin(u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HandError {
+    /// The card would take the hand over the target sum.
+    Bust(Card),
+    /// The card comes after the hand has already won.
+    AlreadyWon(Card),
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HandError::Bust(c) => write!(f, "{} would bust the hand", c),
+            HandError::AlreadyWon(c) => write!(f, "{} comes after the hand has won", c),
+        }
+    }
+}
+
+impl error::Error for HandError {}
+
+impl HandSum {
+    pub fn as_no_win(&self) -> Option<u8> {
+        match *self {
+            HandSum::NoWin(sum) => Some(sum),
+            HandSum::Win(_) => None,
+        }
+    }
+
+    pub fn no_win(&self) -> u8 {
+        self.as_no_win().unwrap_or_else(|| panic!("no_win() called on {:?}", self))
+    }
+}
+
+impl Hand {
+    pub fn new() -> Self {
+        Hand::with_capacity(0)
+    }
+
+    /// An empty hand with room for `n` cards before it reallocates.
+    pub fn with_capacity(n: usize) -> Self {
+        Hand {
+            cards: Cards::with_capacity(n),
+            sum: 0,
+            aces: 0,
+            jokers: 0,
+            specials: 0,
+        }
+    }
+
+    /// Builds a hand from `cards` under the default rules.
+    pub fn from_cards(cards: &[Card]) -> Result<Hand, HandError> {
+        Hand::from_cards_with(cards, &GameRules::default())
+    }
+
+    /// Builds a hand by accepting `cards` in order. Only the last card may
+    /// put the hand in a winning state.
+    pub fn from_cards_with(cards: &[Card], rules: &GameRules) -> Result<Hand, HandError> {
+        let mut hand = Hand::new();
+        for &c in cards {
+            if let HandSum::Win(_) = hand.hand_sum_with(rules) {
+                return Err(HandError::AlreadyWon(c));
+            }
+            if !hand.can_accept_with(c, rules) {
+                return Err(HandError::Bust(c));
+            }
+            hand.push(c);
+        }
+        Ok(hand)
+    }
+
+    /// Builds a hand holding exactly `cards`, however illegal.
+    pub fn from_cards_unchecked(cards: &[Card]) -> Hand {
+        let mut hand = Hand::new();
+        for &c in cards {
+            hand.push(c);
+        }
+        hand
+    }
+
+    fn count(&mut self, card: Card) {
+        match card {
+            Card::Regular(_, n) => {
+                self.sum = self.sum.saturating_add(n);
+                if n == 1 {
+                    self.aces = self.aces.saturating_add(1);
+                }
+            },
+            Card::Joker(_) => self.jokers = self.jokers.saturating_add(1),
+            Card::Special(..) => self.specials = self.specials.saturating_add(1),
+        }
+    }
+
+    /// Adds a card without checking that the hand can accept it.
+    pub(crate) fn push(&mut self, card: Card) {
+        self.count(card);
+        self.cards.push(card);
+    }
+
+    fn recount(&mut self) {
+        self.sum = 0;
+        self.aces = 0;
+        self.jokers = 0;
+        self.specials = 0;
+        for i in 0..self.cards.len() {
+            let c = self.cards[i];
+            self.count(c);
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<Card> {
+        let card = self.cards.pop()?;
+        self.recount();
+        Some(card)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.cards.clear();
+        self.sum = 0;
+        self.aces = 0;
+        self.jokers = 0;
+        self.specials = 0;
+    }
+
+    /// The cards in the order they were accepted, unless `sort` was called.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// The number of cards held; a hand wins on `WinCondition::FiveCards`
+    /// once this reaches `GameRules::cards_to_win`.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// How many cards the hand can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cards.capacity()
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.cards.contains(&card)
+    }
+
+    /// Removes one copy of `card`, returning whether the hand held it. This
+    /// is meant for setting up positions, so removing from a winning hand is
+    /// allowed.
+    pub fn remove(&mut self, card: Card) -> bool {
+        match self.cards.iter().position(|&c| c == card) {
+            Some(i) => {
+                self.cards.remove(i);
+                self.recount();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Sorts the cards in the order given by `Ord for Card`.
+    pub fn sort(&mut self) {
+        self.cards.sort();
+        self.recount();
+    }
+
+    /// Renders the cards followed by the hand's sum under `rules`, or its
+    /// win condition if the hand is winning, e.g. `♠A ♥T ♣3 => 14`.
+    pub fn describe(&self, rules: &GameRules) -> String {
+        let mut res = String::new();
+        for c in self.cards.iter() {
+            res.push_str(&format!("{} ", c));
+        }
+        match self.hand_sum_with(rules) {
+            HandSum::Win(w) => res.push_str(&format!("=> {:?}", w)),
+            HandSum::NoWin(sum) => res.push_str(&format!("=> {}", sum)),
+        }
+        res
+    }
+
+    pub fn can_accept(&self, card: Card) -> bool {
+        self.can_accept_with(card, &GameRules::default())
+    }
+
+    /// Whether the hand may take `card`. A winning hand takes nothing more,
+    /// so this is `false` for it; see `try_can_accept_with` to tell the two
+    /// cases apart.
+    pub fn can_accept_with(&self, card: Card, rules: &GameRules) -> bool {
+        self.try_can_accept_with(card, rules).unwrap_or(false)
+    }
+
+    /// Whether `card` would keep the hand within the target sum, or an
+    /// error if the hand has already won.
+    pub fn try_can_accept_with(&self, card: Card, rules: &GameRules) -> Result<bool, PrugelError> {
+        let sum = match self.hand_sum_with(rules) {
+            HandSum::Win(_) => return Err(PrugelError::WinningHand),
+            HandSum::NoWin(sum) => sum,
+        };
+        match card {
+            Card::Regular(_, n) => Ok(fits(sum, n, rules)),
+            Card::Joker(_) | Card::Special(..) => Ok(true),
+        }
+    }
+
+    /// The regular ranks the hand could accept without busting, in
+    /// increasing order; empty for a winning hand. Jokers and special cards
+    /// are always acceptable, so this covers regular ranks only.
+    pub fn accepting_cards(&self, rules: &GameRules) -> Vec<u8> {
+        match self.hand_sum_with(rules) {
+            HandSum::Win(_) => Vec::new(),
+            HandSum::NoWin(sum) => (1..14).filter(|&n| fits(sum, n, rules)).collect(),
+        }
+    }
+
+    pub fn accept(&mut self, card: Card) {
+        self.accept_with(card, &GameRules::default());
+    }
+
+    pub fn accept_with(&mut self, card: Card, rules: &GameRules) {
+        assert!(self.can_accept_with(card, rules));
+        self.push(card);
+    }
+
+    pub fn hand_sum(&self) -> HandSum {
+        self.hand_sum_with(&GameRules::default())
+    }
+
+    pub fn is_ace_special(&self) -> bool {
+        self.is_ace_special_with(&GameRules::default())
+    }
+
+    /// Whether the hand reaches the target sum by counting one ace as 14
+    /// instead of 1: it holds an ace and sums to `rules.ace_bonus_sum`
+    /// (12 by default) with every ace counted as 1. Only one ace may count
+    /// high, so further aces still count as 1.
+    pub fn is_ace_special_with(&self, rules: &GameRules) -> bool {
+        self.aces >= 1 && self.sum == rules.ace_bonus_sum
+    }
+
+    /// Whether the hand meets `cond`, regardless of any other condition.
+    pub fn satisfies(&self, cond: WinCondition, rules: &GameRules) -> bool {
+        match cond {
+            WinCondition::FiveCards => self.cards.len() >= rules.cards_to_win,
+            WinCondition::TwentyFive => self.sum == rules.target_sum || self.is_ace_special_with(rules),
+            WinCondition::Special => self.specials > 0,
+            WinCondition::Joker => self.jokers > 0,
+        }
+    }
+
+    /// The first condition in `rules.win_precedence` that the hand meets,
+    /// or its sum if it meets none.
+    pub fn hand_sum_with(&self, rules: &GameRules) -> HandSum {
+        for &cond in rules.win_precedence.iter() {
+            if self.satisfies(cond, rules) {
+                return HandSum::Win(cond);
+            }
+        }
+        HandSum::NoWin(self.sum)
+    }
+
+    /// The smallest total of regular ranks that would make the hand win on
+    /// sum or card count, or `None` if no regular cards can. Takes the
+    /// nearest of the target sum, `GameRules::ace_bonus_sum` with an ace
+    /// when that is below the target, and the last card needed to reach
+    /// `GameRules::cards_to_win`; a winning hand is `Some(0)`. Jokers and
+    /// special cards are not considered.
+    pub fn distance_to_win(&self, rules: &GameRules) -> Option<u8> {
+        if let HandSum::Win(_) = self.hand_sum_with(rules) {
+            return Some(0);
+        }
+        if self.sum >= rules.target_sum {
+            return None;
+        }
+        let counts = |cond| rules.win_precedence.contains(&cond);
+        let mut paths = Vec::new();
+        if counts(WinCondition::TwentyFive) {
+            paths.push(rules.target_sum - self.sum);
+            // An ace bonus sum past the target can never be reached.
+            if self.sum < rules.ace_bonus_sum && rules.ace_bonus_sum < rules.target_sum {
+                paths.push(rules.ace_bonus_sum - self.sum);
+            }
+        }
+        if counts(WinCondition::FiveCards) && self.cards.len() + 1 == rules.cards_to_win {
+            paths.push(1);
+        }
+        paths.into_iter().min()
+    }
+}
+
+/// Renders the hand under the default rules; see `Hand::describe`.
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe(&GameRules::default()))
+    }
+}
+
+/// Whether a card of rank `n` keeps a hand summing to `sum` within the
+/// target, without overflowing for targets near 255.
+fn fits(sum: u8, n: u8, rules: &GameRules) -> bool {
+    sum.checked_add(n).is_some_and(|s| s <= rules.target_sum)
+}
@@ -0,0 +1,50 @@
+extern crate rand;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "logging")]
+#[macro_use]
+extern crate log;
+
+// Without the `logging` feature the log macros still type-check their
+// arguments but compile to nothing.
+#[cfg(not(feature = "logging"))]
+macro_rules! debug {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
+#[cfg(not(feature = "logging"))]
+macro_rules! trace {
+    ($($arg:tt)*) => { if false { let _ = format_args!($($arg)*); } };
+}
+
+mod builder;
+mod card;
+mod deck;
+mod error;
+mod game;
+mod hand;
+mod observer;
+mod rules;
+mod seed;
+mod stats;
+mod strategy;
+mod tournament;
+
+pub use builder::{BuildError, GameBuilder};
+pub use card::{Card, CardError, Color, ParseCardError, Suit, DEFAULT_SPECIALS};
+pub use deck::Deck;
+pub use error::PrugelError;
+pub use game::{DealError, Game, GameSnapshot, InvariantError, NamesError, ParseRecordError,
+               ReceiverPreview, ReplayError, RoundResult, StepError, StepOutcome, MATCH_GAMES_LIMIT,
+               ROUNDS_UNTIL_WIN_LIMIT};
+pub use hand::{Hand, HandError, HandSum, WinCondition};
+pub use observer::GameObserver;
+pub use rules::GameRules;
+pub use seed::seed_from_str;
+pub use stats::SimulationStats;
+pub use strategy::{Bias, BiasedRandomStrategy, ExpectimaxStrategy, FirstEligibleStrategy,
+                   GreedyStrategy, HumanStrategy, PlayerStrategies, RandomStrategy,
+                   ScriptedStrategy, Strategy};
+pub use tournament::Tournament;
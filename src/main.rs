@@ -1,350 +1,155 @@
-extern crate rand;
-use rand::Rng;
-use rand::SeedableRng;
-use std::fmt;
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Suit {
-    Spades,
-    Hearts,
-    Clubs,
-    Diamonds,
-}
-
-impl Suit {
-    fn is_red(&self) -> bool {
-        *self == Suit::Hearts || *self == Suit::Diamonds
-    }
-}
-
-impl fmt::Display for Suit {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Suit::Spades => write!(f, "\u{2660}"),
-            Suit::Hearts => write!(f, "\u{2665}"),
-            Suit::Clubs => write!(f, "\u{2663}"),
-            Suit::Diamonds => write!(f, "\u{2666}"),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Card {
-    Regular(Suit, u8),
-    Joker(u8),
-    Special(Suit),
-}
-
-impl fmt::Display for Card {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Card::Regular(s, 1) => write!(f, "{}A", s),
-            Card::Regular(s, 13) => write!(f, "{}K", s),
-            Card::Regular(s, 12) => write!(f, "{}Q", s),
-            Card::Regular(s, 11) => write!(f, "{}J", s),
-            Card::Regular(s, 10) => write!(f, "{}T", s),
-            Card::Regular(s, n) => write!(f, "{}{}", s, n),
-            Card::Joker(n) => write!(f, "J{}", n),
-            Card::Special(s) => write!(f, "{}{}", s, if s == Suit::Spades { "J" } else { "Q" }),
-        }
-    }
-}
-
-impl Card {
-    fn new(suit: Suit, num: u8) -> Self {
-        assert!(1 <= num && num < 14);
-        if num == 12 && suit == Suit::Diamonds {
-            Card::Special(suit)
-        } else if num == 11 && suit == Suit::Spades {
-            Card::Special(suit)
-        } else {
-            Card::Regular(suit, num)
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Deck {
-    cards: Vec<Card>,
-}
-
-impl Deck {
-    fn empty() -> Deck {
-        Deck {
-            cards: Vec::new(),
-        }
-    }
-
-    fn new(jokers: u8) -> Deck {
-        let mut res = Vec::new();
-        for &suit in &[Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds] {
-            for num in 1..14 {
-                res.push(Card::new(suit, num));
+extern crate clap;
+extern crate prugel;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_json;
+
+use std::process;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use prugel::{ExpectimaxStrategy, FirstEligibleStrategy, Game, GameBuilder, GameRules,
+             GreedyStrategy, HumanStrategy, RandomStrategy, RoundResult, StepOutcome, Strategy,
+             WinCondition};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum StrategyKind {
+    /// Pick a random eligible player.
+    Random,
+    /// Always pick the lowest eligible player.
+    First,
+    /// Keep the receiving hand as far from winning as possible.
+    Greedy,
+    /// Search two draws ahead.
+    Expectimax,
+    /// Ask on standard input.
+    Human,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    /// One line per round as given by `describe`.
+    Text,
+    /// One JSON object per round, or for the summary.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+/// Plays a game of prugel and prints every round, or a summary at the end.
+#[derive(Debug, Parser)]
+#[command(name = "prugel")]
+struct Args {
+    /// Number of players.
+    #[arg(long, default_value_t = 5)]
+    players: usize,
+    /// Number of jokers in the deck.
+    #[arg(long, default_value_t = 3)]
+    jokers: u8,
+    /// Seed for shuffling and reshuffling the deck.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    /// Number of rounds to play.
+    #[arg(long, default_value_t = 1000)]
+    rounds: usize,
+    /// Who decides which eligible player receives each card.
+    #[arg(long, value_enum, default_value_t = StrategyKind::Random)]
+    strategy: StrategyKind,
+    /// Print only the number of rounds and wins at the end.
+    #[arg(long)]
+    summary: bool,
+    /// How to print each round and the summary.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+const CONDITIONS: [WinCondition; 4] = [WinCondition::FiveCards, WinCondition::TwentyFive,
+                                       WinCondition::Special, WinCondition::Joker];
+
+/// How many of the game's recorded wins were by each of `CONDITIONS`.
+fn condition_counts(g: &Game) -> Vec<usize> {
+    CONDITIONS.iter()
+        .map(|&cond| g.history().iter().filter(|r| r.win == Some(cond)).count())
+        .collect()
+}
+
+fn print_round(args: &Args, g: &Game, result: &RoundResult) {
+    match args.format {
+        Format::Text => println!("{}", result.describe(g)),
+        #[cfg(feature = "serde")]
+        Format::Json => println!("{}", serde_json::to_string(result).unwrap()),
+    }
+}
+
+fn print_summary(args: &Args, g: &Game) {
+    let counts = condition_counts(g);
+    match args.format {
+        Format::Text => {
+            println!("rounds: {}", g.round());
+            for (player, wins) in g.wins().iter().enumerate() {
+                println!("player {}: {} wins", player, wins);
             }
-        }
-        for i in 0..jokers {
-            res.push(Card::Joker(i));
-        }
-        Deck {
-            cards: res,
-        }
-    }
-
-    fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
-        rng.shuffle(&mut self.cards);
-    }
-
-    fn push(&mut self, card: Card) {
-        self.cards.push(card);
-    }
-
-    fn pop(&mut self) -> Option<Card> {
-        self.cards.pop()
-    }
-
-    fn take(&mut self, hand: &mut Hand) {
-        for c in hand.cards.iter() {
-            self.cards.push(*c);
-        }
-        hand.cards.clear();
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Hand {
-    cards: Vec<Card>,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum WinCondition {
-    FiveCards,
-    TwentyFive,
-    Special,
-    Joker,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum HandSum {
-    Win(WinCondition),
-    NoWin(u8),
-}
-
-impl HandSum {
-    fn no_win(&self) -> u8 {
-        match *self {
-            HandSum::NoWin(sum) => sum,
-            _ => panic!("no_win() called on {:?}", self),
-        }
-    }
-}
-
-impl Hand {
-    fn new() -> Self {
-        Hand {
-            cards: Vec::new(),
-        }
-    }
-
-    fn can_accept(&self, card: Card) -> bool {
-        let n = match card {
-            Card::Regular(_, n) => n,
-            Card::Joker(_) | Card::Special(_) => return true,
-        };
-        match self.hand_sum() {
-            HandSum::Win(_) => panic!("can_accept() on winning hand"),
-            HandSum::NoWin(sum) => sum + n <= 25
-        }
-    }
-
-    fn accept(&mut self, card: Card) {
-        assert!(self.can_accept(card));
-        self.cards.push(card);
-    }
-
-    fn hand_sum(&self) -> HandSum {
-        let mut sum = 0;
-        let mut aces = 0;
-        for c in self.cards.iter() {
-            match c {
-                Card::Regular(_, n) => {sum += n; if *n == 1 { aces += 1; }},
-                Card::Joker(_) => return HandSum::Win(WinCondition::Joker),
-                Card::Special(_) => return HandSum::Win(WinCondition::Special),
-            }
-        }
-        if self.cards.len() == 5 {
-            HandSum::Win(WinCondition::FiveCards)
-        } else if sum == 25 || (sum == 12 && aces >= 1) {
-            HandSum::Win(WinCondition::TwentyFive)
-        } else {
-            HandSum::NoWin(sum)
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Game {
-    deck: Deck,
-    discard: Deck,
-    players: Vec<Hand>,
-    round: usize,
-}
-
-#[derive(Debug, Clone)]
-struct RoundResult {
-    giver: usize,
-    receiver: Option<usize>,
-    card: Card,
-    win: Option<WinCondition>,
-}
-
-impl RoundResult {
-    fn describe(&self, g: &Game) -> String {
-        match (self.receiver, self.win) {
-            (Some(r), None) =>
-                format!("{} {} to {} => {}",
-                        self.giver, self.card, r, g.players[r].hand_sum().no_win()),
-            (Some(r), Some(w)) =>
-                format!("{} {} to {} => {:?}",
-                        self.giver, self.card, r, w),
-            (None, _) =>
-                format!("{} {} to nobody", self.giver, self.card),
-        }
-    }
-}
-
-trait Strategy {
-    fn choose(&mut self, giver: usize, hands: &Vec<Hand>, card: Card) -> usize;
-}
-
-impl Game {
-    fn new(players: usize, jokers: u8) -> Self {
-        let mut hands = Vec::new();
-        hands.resize(players, Hand::new());
-        Game {
-            deck: Deck::new(jokers),
-            discard: Deck::empty(),
-            players: hands,
-            round: 0,
-        }
-    }
-
-    fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
-        self.deck.shuffle(rng);
-    }
-
-    fn pop_deck<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<Card> {
-        match self.deck.pop() {
-            Some(c) => Some(c),
-            None => {
-                std::mem::swap(&mut self.deck, &mut self.discard);
-                self.deck.shuffle(rng);
-                self.deck.pop()
-            }
-        }
-    }
-
-    fn step<R: Rng + ?Sized, S: Strategy>(&mut self, rng: &mut R, strategy: &mut S)
-            -> Option<RoundResult> {
-        let card = match self.pop_deck(rng) {
-            Some(c) => c,
-            None => return None,
-        };
-        let is_red = match card {
-            Card::Regular(s, _) => s.is_red(),
-            Card::Joker(_) | Card::Special(_) => false,
-        };
-        let giver = self.round % self.players.len();
-        let receiver = if is_red {
-            let mut one = None;
-            let mut n = 0;
-            for (i, hand) in self.players.iter().enumerate() {
-                if hand.can_accept(card) {
-                    one = Some(i);
-                    n += 1;
-                }
-            }
-            match n {
-                0 => None,
-                1 => one,
-                _ => {
-                    let j = strategy.choose(giver, &self.players, card);
-                    assert!(j < self.players.len());
-                    assert!(self.players[j].can_accept(card));
-                    Some(j)
-                },
-            }
-        } else {
-            let i = giver;
-            if self.players[i].can_accept(card) {
-                Some(i)
-            } else {
-                None
+            for (cond, count) in CONDITIONS.iter().zip(counts) {
+                println!("{:?}: {}", cond, count);
             }
-        };
-        let mut win = None;
-        match receiver {
-            Some(i) => match card {
-                Card::Special(_) => {
-                    win = Some(WinCondition::Special);
-                    self.discard.push(card);
-                },
-                _ => {
-                    self.players[i].accept(card);
-                    if let HandSum::Win(cond) = self.players[i].hand_sum() {
-                        self.discard.take(&mut self.players[i]);
-                        win = Some(cond);
-                    }
-                }
+        },
+        #[cfg(feature = "serde")]
+        Format::Json => {
+            let conditions: serde_json::Map<String, serde_json::Value> = CONDITIONS.iter()
+                .zip(counts)
+                .map(|(cond, count)| (format!("{:?}", cond), count.into()))
+                .collect();
+            let summary = json!({
+                "rounds": g.round(),
+                "wins": g.wins(),
+                "conditions": conditions,
+            });
+            println!("{}", summary);
+        },
+    }
+}
+
+/// Plays `args.rounds` rounds. A game that ends early is reported on
+/// standard error and the process exits with status 1.
+fn play<S: Strategy>(args: &Args, mut g: Game, mut strategy: S) {
+    let mut ended = None;
+    for _ in 0..args.rounds {
+        match g.step(&mut strategy).expect("invalid strategy choice") {
+            StepOutcome::Played(_) if args.summary => (),
+            StepOutcome::Played(result) => print_round(args, &g, &result),
+            StepOutcome::Exhausted => {
+                ended = Some("We're out of cards!");
+                break;
+            },
+            StepOutcome::Stalemate => {
+                ended = Some("Nobody can take any more cards!");
+                break;
             },
-            None => self.discard.push(card),
-        };
-        self.round += 1;
-        Some(RoundResult {
-            giver: giver,
-            receiver: receiver,
-            card: card,
-            win: win,
-        })
-    }
-}
-
-struct RandomStrategy {
-    rng: rand::prng::XorShiftRng,
-    tmp_players: Vec<usize>,
-}
-
-impl RandomStrategy {
-    fn new() -> Self {
-        RandomStrategy {
-            rng: rand::prng::XorShiftRng::from_seed([60; 16]),
-            tmp_players: Vec::new(),
         }
     }
-}
-
-impl Strategy for RandomStrategy {
-    fn choose(&mut self, _giver: usize, hands: &Vec<Hand>, card: Card) -> usize {
-        self.tmp_players.clear();
-        for (i, hand) in hands.iter().enumerate() {
-            if hand.can_accept(card) {
-                self.tmp_players.push(i);
-            }
-        }
-        *self.rng.choose(&self.tmp_players).unwrap()
+    if args.summary {
+        print_summary(args, &g);
+    }
+    if let Some(message) = ended {
+        eprintln!("{}", message);
+        process::exit(1);
     }
 }
 
 fn main() {
-    let seed = 42;
-    let players = 5;
-    let jokers = 3;
-    let mut strategy = RandomStrategy::new();
-
-    let mut g = Game::new(players, jokers);
-    let mut rng = rand::prng::XorShiftRng::from_seed([seed; 16]);
-    g.shuffle(&mut rng);
-    for _ in 0..1000 {
-        let result = g.step(&mut rng, &mut strategy).expect("We're out of cards!");
-        println!("{}", result.describe(&g));
+    let args = Args::parse();
+    let rules = GameRules::default();
+    let g = GameBuilder::new()
+        .players(args.players)
+        .jokers(args.jokers)
+        .rules(rules.clone())
+        .seed(args.seed)
+        .build()
+        .unwrap_or_else(|e| Args::command().error(ErrorKind::InvalidValue, e).exit());
+    match args.strategy {
+        StrategyKind::Random => play(&args, g, RandomStrategy::from_seed([60; 16])),
+        StrategyKind::First => play(&args, g, FirstEligibleStrategy),
+        StrategyKind::Greedy => play(&args, g, GreedyStrategy::new(rules)),
+        StrategyKind::Expectimax => play(&args, g, ExpectimaxStrategy::new(rules, 2)),
+        StrategyKind::Human => play(&args, g, HumanStrategy::stdio(rules)),
     }
 }
@@ -0,0 +1,22 @@
+use card::Card;
+use hand::WinCondition;
+
+/// Receives the events of `Game::step_observed` as they happen. Every method
+/// does nothing by default.
+pub trait GameObserver {
+    /// The discard pile was shuffled into the empty deck. Reported just
+    /// before the draw of the next round that is played.
+    fn on_reshuffle(&mut self) {}
+
+    /// A card was drawn for a round that is played; a draw undone because
+    /// the strategy chose an ineligible player is not reported.
+    fn on_draw(&mut self, _card: Card) {}
+
+    /// A player received the card. Special cards win at once and go
+    /// straight to the discard pile instead of staying in the hand.
+    fn on_accept(&mut self, _player: usize, _card: Card) {}
+
+    fn on_win(&mut self, _player: usize, _condition: WinCondition) {}
+}
+
+impl GameObserver for () {}
@@ -0,0 +1,60 @@
+use card::{Suit, DEFAULT_SPECIALS};
+use hand::WinCondition;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameRules {
+    /// A hand whose regular cards sum to exactly this value wins.
+    pub target_sum: u8,
+    /// A hand containing at least one ace also wins at this sum, since one
+    /// ace may count as 14 rather than 1. This is normally `target_sum - 13`.
+    pub ace_bonus_sum: u8,
+    /// After this many consecutive rounds in which nobody could take the
+    /// card, the game is a stalemate and `Game::step` stops playing.
+    /// `None` disables the check.
+    pub stalemate_rounds: Option<usize>,
+    /// The `(suit, rank)` pairs dealt as `Card::Special`.
+    pub specials: Vec<(Suit, u8)>,
+    /// The order in which win conditions are checked, so a hand meeting
+    /// several wins with the first one listed. A condition left out of the
+    /// list never wins. Defaults to joker, special, five cards, target sum.
+    pub win_precedence: Vec<WinCondition>,
+    /// Whether jokers are routed like red cards, so that any player may
+    /// receive them, rather than only the giver.
+    pub joker_is_red: bool,
+    /// A hand holding this many cards wins with `WinCondition::FiveCards`.
+    pub cards_to_win: usize,
+    /// Whether a special card that wins leaves the game for good rather
+    /// than going to the discard pile and returning on a reshuffle.
+    pub consume_special_on_win: bool,
+    /// Scores that replace `WinCondition::points` for the listed
+    /// conditions.
+    pub win_points: Vec<(WinCondition, u32)>,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            target_sum: 25,
+            ace_bonus_sum: 12,
+            stalemate_rounds: Some(1000),
+            specials: DEFAULT_SPECIALS.to_vec(),
+            win_precedence: vec![WinCondition::Joker, WinCondition::Special,
+                                 WinCondition::FiveCards, WinCondition::TwentyFive],
+            joker_is_red: false,
+            cards_to_win: 5,
+            consume_special_on_win: false,
+            win_points: Vec::new(),
+        }
+    }
+}
+
+impl GameRules {
+    /// What a win by `cond` scores under these rules.
+    pub fn points(&self, cond: WinCondition) -> u32 {
+        match self.win_points.iter().find(|&&(c, _)| c == cond) {
+            Some(&(_, points)) => points,
+            None => cond.points(),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+pub(crate) fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Hashes a label such as `"experiment-3"` into a 16-byte RNG seed.
+///
+/// The mapping is part of the crate's stable interface and will not change
+/// between versions, so a run named by its label stays reproducible: the
+/// first eight bytes are the little-endian FNV-1a hash of the label, and the
+/// last eight continue that hash over the label a second time.
+pub fn seed_from_str(label: &str) -> [u8; 16] {
+    let first = fnv1a(FNV_OFFSET, label.as_bytes());
+    let second = fnv1a(first, label.as_bytes());
+    let mut res = [0; 16];
+    for i in 0..8 {
+        res[i] = (first >> (8 * i)) as u8;
+        res[i + 8] = (second >> (8 * i)) as u8;
+    }
+    res
+}
+
+pub(crate) fn seed_bytes(seed: u64) -> [u8; 16] {
+    let mut res = [0; 16];
+    for (i, b) in res.iter_mut().enumerate() {
+        *b = (seed >> (8 * (i % 8))) as u8;
+    }
+    res
+}
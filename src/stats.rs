@@ -0,0 +1,80 @@
+use rand::SeedableRng;
+use rand::prng::XorShiftRng;
+
+use game::Game;
+use hand::WinCondition;
+use rules::GameRules;
+use seed::seed_bytes;
+use strategy::RandomStrategy;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationStats {
+    games: usize,
+    conditions: [u64; 4],
+    player_wins: Vec<u64>,
+    rounds_to_win: u64,
+}
+
+fn condition_index(cond: WinCondition) -> usize {
+    match cond {
+        WinCondition::FiveCards => 0,
+        WinCondition::TwentyFive => 1,
+        WinCondition::Special => 2,
+        WinCondition::Joker => 3,
+    }
+}
+
+impl SimulationStats {
+    /// Plays `games` games until their first win with `RandomStrategy`,
+    /// seeding game `i` from `base_seed + i`.
+    pub fn run(players: usize, jokers: u8, rules: GameRules, games: usize, base_seed: u64)
+            -> SimulationStats {
+        assert!(players >= 2, "need at least 2 players, got {}", players);
+        let mut stats = SimulationStats {
+            games,
+            conditions: [0; 4],
+            player_wins: vec![0; players],
+            rounds_to_win: 0,
+        };
+        for i in 0..games {
+            let seed = base_seed.wrapping_add(i as u64);
+            let mut rng = XorShiftRng::from_seed(seed_bytes(seed));
+            let mut strategy = RandomStrategy::from_seed(seed_bytes(!seed));
+            let mut game = Game::with_rules(players, 1, jokers, rules.clone());
+            game.shuffle_with_rng(&mut rng);
+            let win = game.run_until_win(&mut rng, &mut strategy)
+                .expect("random strategy only picks eligible players");
+            if let Some((player, cond)) = win {
+                stats.conditions[condition_index(cond)] += 1;
+                stats.player_wins[player] += 1;
+                stats.rounds_to_win += game.history().len() as u64;
+            }
+        }
+        stats
+    }
+
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Number of games that ended in a win.
+    pub fn wins(&self) -> u64 {
+        self.conditions.iter().sum()
+    }
+
+    pub fn condition_count(&self, cond: WinCondition) -> u64 {
+        self.conditions[condition_index(cond)]
+    }
+
+    pub fn player_wins(&self) -> &[u64] {
+        &self.player_wins
+    }
+
+    /// Average number of rounds played in the games that ended in a win.
+    pub fn average_rounds_to_win(&self) -> Option<f64> {
+        match self.wins() {
+            0 => None,
+            n => Some(self.rounds_to_win as f64 / n as f64),
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use std::io;
+use std::io::{BufRead, Write};
+
+use rand::Rng;
+use rand::SeedableRng;
+use rand::prng::XorShiftRng;
+
+use card::Card;
+use deck::Deck;
+use error::PrugelError;
+use hand::{Hand, HandSum};
+use rules::GameRules;
+
+/// Decides who receives a red card that several players could take. The
+/// decision is the giver's; to give each player a policy of their own,
+/// see `PlayerStrategies`.
+pub trait Strategy {
+    /// Picks who receives `card` among the `eligible` players, of which
+    /// there are always at least two. `eligible` is sorted by index.
+    fn choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize]) -> usize;
+
+    /// Like `choose`, but checks both the input and the strategy's answer
+    /// instead of trusting them.
+    fn try_choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize])
+            -> Result<usize, PrugelError> {
+        if eligible.is_empty() {
+            return Err(PrugelError::NoEligiblePlayers);
+        }
+        let i = self.choose(giver, hands, card, eligible);
+        if eligible.contains(&i) { Ok(i) } else { Err(PrugelError::IneligibleChoice(i)) }
+    }
+}
+
+pub struct RandomStrategy<R: Rng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomStrategy<R> {
+    pub fn new(rng: R) -> Self {
+        RandomStrategy {
+            rng,
+        }
+    }
+}
+
+impl RandomStrategy<XorShiftRng> {
+    pub fn from_seed(seed: [u8; 16]) -> Self {
+        RandomStrategy::new(XorShiftRng::from_seed(seed))
+    }
+}
+
+impl Default for RandomStrategy<XorShiftRng> {
+    fn default() -> Self {
+        RandomStrategy::from_seed([60; 16])
+    }
+}
+
+impl<R: Rng> Strategy for RandomStrategy<R> {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, eligible: &[usize]) -> usize {
+        *self.rng.choose(eligible).unwrap()
+    }
+}
+
+/// How `BiasedRandomStrategy` weights the eligible players.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bias {
+    /// Every eligible player is equally likely, as with `RandomStrategy`.
+    Uniform,
+    /// A player is weighted by their hand's sum plus one, favouring the
+    /// hands closest to the target sum.
+    HighSums,
+    /// A player is weighted by their distance around the table from the
+    /// giver plus one, favouring the seats furthest away.
+    FarSeats,
+}
+
+/// Picks a random eligible player, weighted by `Bias`.
+pub struct BiasedRandomStrategy<R: Rng> {
+    rng: R,
+    bias: Bias,
+    rules: GameRules,
+}
+
+impl<R: Rng> BiasedRandomStrategy<R> {
+    pub fn new(rng: R, bias: Bias, rules: GameRules) -> Self {
+        BiasedRandomStrategy {
+            rng,
+            bias,
+            rules,
+        }
+    }
+
+    fn weight(&self, giver: usize, hands: &[Hand], i: usize) -> u32 {
+        match self.bias {
+            Bias::Uniform => 1,
+            Bias::HighSums => match hands[i].hand_sum_with(&self.rules) {
+                HandSum::NoWin(sum) => sum as u32 + 1,
+                HandSum::Win(_) => 1,
+            },
+            Bias::FarSeats => {
+                let ahead = (i + hands.len() - giver) % hands.len();
+                ahead.min(hands.len() - ahead) as u32 + 1
+            },
+        }
+    }
+}
+
+impl<R: Rng> Strategy for BiasedRandomStrategy<R> {
+    fn choose(&mut self, giver: usize, hands: &[Hand], _card: Card, eligible: &[usize]) -> usize {
+        if self.bias == Bias::Uniform {
+            return *self.rng.choose(eligible).unwrap();
+        }
+        let total: u32 = eligible.iter().map(|&i| self.weight(giver, hands, i)).sum();
+        let mut pick = self.rng.gen_range(0, total);
+        for &i in eligible {
+            let w = self.weight(giver, hands, i);
+            if pick < w {
+                return i;
+            }
+            pick -= w;
+        }
+        unreachable!("pick is below the total weight")
+    }
+}
+
+/// One strategy per player, indexed like the game's players. Each decision
+/// goes to the strategy of the player giving the card. Panics if a player
+/// has no strategy.
+pub struct PlayerStrategies {
+    strategies: Vec<Box<dyn Strategy>>,
+}
+
+impl PlayerStrategies {
+    pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        PlayerStrategies {
+            strategies,
+        }
+    }
+
+    /// Moves every strategy one seat down, so player `i` gets the strategy
+    /// player `i + 1` had.
+    pub(crate) fn rotate(&mut self) {
+        self.strategies.rotate_left(1);
+    }
+}
+
+impl Strategy for PlayerStrategies {
+    fn choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize]) -> usize {
+        let n = self.strategies.len();
+        let strategy = self.strategies.get_mut(giver)
+            .unwrap_or_else(|| panic!("no strategy for player {} of {}", giver, n));
+        strategy.choose(giver, hands, card, eligible)
+    }
+}
+
+/// Always picks the lowest eligible index. Needs no RNG, so a game played
+/// with it is fully determined by the deck order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstEligibleStrategy;
+
+impl Strategy for FirstEligibleStrategy {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, eligible: &[usize]) -> usize {
+        eligible[0]
+    }
+}
+
+/// Avoids handing an opponent a winning card. Among the remaining options
+/// it takes a win for the giver if one is available, and otherwise keeps
+/// the receiving hand as far from the target sum as possible. If every
+/// option wins for an opponent, it picks the lowest eligible index.
+#[derive(Debug, Clone, Default)]
+pub struct GreedyStrategy {
+    rules: GameRules,
+}
+
+impl GreedyStrategy {
+    pub fn new(rules: GameRules) -> Self {
+        GreedyStrategy {
+            rules,
+        }
+    }
+}
+
+impl Strategy for GreedyStrategy {
+    fn choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize]) -> usize {
+        let mut best = None;
+        for &i in eligible {
+            let mut after = hands[i].clone();
+            after.accept_with(card, &self.rules);
+            let score = match after.hand_sum_with(&self.rules) {
+                HandSum::Win(_) if i == giver => u16::MAX,
+                HandSum::Win(_) => continue,
+                HandSum::NoWin(sum) => (self.rules.target_sum - sum) as u16,
+            };
+            if best.is_none_or(|(_, s)| score > s) {
+                best = Some((i, score));
+            }
+        }
+        best.map_or(eligible[0], |(i, _)| i)
+    }
+}
+
+/// Looks `depth` draws past the current card and picks the receiver with
+/// the best expected outcome for the giver: +1 if the giver wins within the
+/// horizon, -1 if an opponent does, and 0 otherwise. Opponents are assumed
+/// to play against the giver, and each future card is assumed to be drawn
+/// from a full deck under the rules, ignoring jokers and the cards already
+/// held. The cost grows by a factor of about 28 times the number of players
+/// per level, so keep `depth` small. Ties go to the lowest eligible index.
+#[derive(Debug, Clone)]
+pub struct ExpectimaxStrategy {
+    rules: GameRules,
+    depth: usize,
+    /// One representative of each kind of card that plays differently,
+    /// with its probability of being drawn.
+    outcomes: Vec<(Card, f64)>,
+}
+
+impl ExpectimaxStrategy {
+    pub fn new(rules: GameRules, depth: usize) -> Self {
+        let deck = Deck::with_rules(1, 0, &rules);
+        let mut outcomes: Vec<(Card, f64)> = Vec::new();
+        for &card in deck.iter() {
+            let p = 1.0 / deck.len() as f64;
+            let same = |c: &Card| c.is_red() == card.is_red() && c.rank() == card.rank()
+                && c.is_special() == card.is_special();
+            match outcomes.iter_mut().find(|o| same(&o.0)) {
+                Some(o) => o.1 += p,
+                None => outcomes.push((card, p)),
+            }
+        }
+        ExpectimaxStrategy {
+            rules,
+            depth,
+            outcomes,
+        }
+    }
+
+    /// The value for `me` of `receiver` taking `card`, followed by `draws`
+    /// more rounds.
+    fn play(&self, me: usize, hands: &[Hand], giver: usize, card: Card, receiver: Option<usize>,
+            draws: usize) -> f64 {
+        let mut hands = hands.to_vec();
+        if let Some(i) = receiver {
+            let sign = if i == me { 1.0 } else { -1.0 };
+            hands[i].accept_with(card, &self.rules);
+            if let HandSum::Win(_) = hands[i].hand_sum_with(&self.rules) {
+                return sign;
+            }
+        }
+        if draws == 0 {
+            return 0.0;
+        }
+        let giver = (giver + 1) % hands.len();
+        let mut value = 0.0;
+        for &(card, p) in self.outcomes.iter() {
+            let values: Vec<f64> = (0..hands.len())
+                .filter(|&i| (card.is_red() || i == giver)
+                        && hands[i].can_accept_with(card, &self.rules))
+                .map(|i| self.play(me, &hands, giver, card, Some(i), draws - 1))
+                .collect();
+            value += p * if values.is_empty() {
+                self.play(me, &hands, giver, card, None, draws - 1)
+            } else if giver == me {
+                values.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+            } else {
+                values.iter().cloned().fold(f64::INFINITY, f64::min)
+            };
+        }
+        value
+    }
+}
+
+impl Strategy for ExpectimaxStrategy {
+    fn choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize]) -> usize {
+        let mut best = None;
+        for &i in eligible {
+            let value = self.play(giver, hands, giver, card, Some(i), self.depth);
+            if best.is_none_or(|(_, v)| value > v) {
+                best = Some((i, value));
+            }
+        }
+        best.map_or(eligible[0], |(i, _)| i)
+    }
+}
+
+/// Replays a fixed sequence of choices, one per decision point.
+/// Panics if the script runs out or names an ineligible player.
+#[derive(Debug, Clone)]
+pub struct ScriptedStrategy {
+    choices: Vec<usize>,
+    pos: usize,
+}
+
+impl ScriptedStrategy {
+    pub fn new(choices: Vec<usize>) -> Self {
+        ScriptedStrategy {
+            choices,
+            pos: 0,
+        }
+    }
+}
+
+impl Strategy for ScriptedStrategy {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], card: Card, eligible: &[usize]) -> usize {
+        let i = *self.choices.get(self.pos)
+            .unwrap_or_else(|| panic!("script ran out after {} choices", self.pos));
+        assert!(eligible.contains(&i), "scripted choice {} cannot accept {}", i, card);
+        self.pos += 1;
+        i
+    }
+}
+
+/// Asks a human which eligible player receives the card. On end of input
+/// (or an I/O error) it falls back to the lowest eligible index.
+pub struct HumanStrategy<I: BufRead, O: Write> {
+    input: I,
+    output: O,
+    rules: GameRules,
+}
+
+impl<I: BufRead, O: Write> HumanStrategy<I, O> {
+    pub fn new(input: I, output: O, rules: GameRules) -> Self {
+        HumanStrategy {
+            input,
+            output,
+            rules,
+        }
+    }
+
+    fn prompt(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize])
+            -> io::Result<Option<usize>> {
+        writeln!(self.output, "Player {} gives {}. Who receives it?", giver, card)?;
+        for &i in eligible {
+            match hands[i].hand_sum_with(&self.rules).as_no_win() {
+                Some(sum) => writeln!(self.output, "  {}: {}", i, sum)?,
+                None => writeln!(self.output, "  {}", i)?,
+            }
+        }
+        let mut line = String::new();
+        loop {
+            write!(self.output, "> ")?;
+            self.output.flush()?;
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+            match line.trim().parse() {
+                Ok(i) if eligible.contains(&i) => return Ok(Some(i)),
+                _ => writeln!(self.output, "Please enter one of {:?}", eligible)?,
+            }
+        }
+    }
+}
+
+impl HumanStrategy<io::StdinLock<'static>, io::Stdout> {
+    pub fn stdio(rules: GameRules) -> Self {
+        HumanStrategy::new(io::stdin().lock(), io::stdout(), rules)
+    }
+}
+
+impl<I: BufRead, O: Write> Strategy for HumanStrategy<I, O> {
+    fn choose(&mut self, giver: usize, hands: &[Hand], card: Card, eligible: &[usize]) -> usize {
+        match self.prompt(giver, hands, card, eligible) {
+            Ok(Some(i)) => i,
+            Ok(None) | Err(_) => eligible[0],
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use rand::SeedableRng;
+use rand::prng::XorShiftRng;
+
+use game::{Game, StepError};
+use rules::GameRules;
+use seed::seed_bytes;
+use strategy::{PlayerStrategies, Strategy};
+
+/// Plays strategies against each other, one per seat.
+///
+/// As with `PlayerStrategies`, the giver of a red card decides who gets it,
+/// so each strategy is only consulted on the rounds its own seat gives.
+/// Seats rotate from game to game: in game `i`, seat `s` is played by
+/// strategy `(s + i) % n`, so over a multiple of `n` games every strategy
+/// sits in every seat equally often.
+pub struct Tournament {
+    players: PlayerStrategies,
+    jokers: u8,
+    rules: GameRules,
+    games: usize,
+    wins: Vec<u64>,
+}
+
+impl Tournament {
+    /// Panics unless there are at least two strategies.
+    pub fn new(strategies: Vec<Box<dyn Strategy>>) -> Self {
+        assert!(strategies.len() >= 2, "need at least 2 strategies, got {}", strategies.len());
+        let n = strategies.len();
+        Tournament {
+            players: PlayerStrategies::new(strategies),
+            jokers: 0,
+            rules: GameRules::default(),
+            games: 0,
+            wins: vec![0; n],
+        }
+    }
+
+    pub fn jokers(mut self, jokers: u8) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    pub fn rules(mut self, rules: GameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Plays `games` more games until their first win, seeding game `i`
+    /// from `base_seed + i`. Stops at the first invalid strategy choice.
+    pub fn run(&mut self, games: usize, base_seed: u64) -> Result<(), StepError> {
+        let n = self.wins.len();
+        for i in 0..games {
+            let seed = base_seed.wrapping_add(i as u64);
+            let mut rng = XorShiftRng::from_seed(seed_bytes(seed));
+            let mut game = Game::with_rules(n, 1, self.jokers, self.rules.clone());
+            game.shuffle_with_rng(&mut rng);
+            let offset = self.games % n;
+            let win = game.run_until_win(&mut rng, &mut self.players)?;
+            self.players.rotate();
+            self.games += 1;
+            if let Some((seat, _)) = win {
+                self.wins[(seat + offset) % n] += 1;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Games won by each strategy, in the order they were given.
+    pub fn wins(&self) -> &[u64] {
+        &self.wins
+    }
+}
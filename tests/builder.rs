@@ -0,0 +1,30 @@
+extern crate prugel;
+
+use prugel::{BuildError, GameBuilder, GameRules, RandomStrategy};
+
+#[test]
+fn builder_validates_players() {
+    assert_eq!(GameBuilder::new().players(1).build().unwrap_err(), BuildError::TooFewPlayers(1));
+    assert!(GameBuilder::new().players(2).build().is_ok());
+}
+
+#[test]
+fn builder_seed_is_reproducible() {
+    let rules = GameRules { target_sum: 21, ..GameRules::default() };
+    let a = GameBuilder::new().players(3).jokers(2).rules(rules.clone()).seed(9).build().unwrap();
+    let b = GameBuilder::new().players(3).jokers(2).rules(rules.clone()).seed(9).build().unwrap();
+    let c = GameBuilder::new().players(3).jokers(2).rules(rules.clone()).seed(10).build().unwrap();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(*a.rules(), rules);
+}
+
+#[test]
+fn seeded_games_play_identically() {
+    let play = || {
+        let mut g = GameBuilder::new().players(4).jokers(3).seed(11).build().unwrap();
+        let mut strategy = RandomStrategy::from_seed([12; 16]);
+        (0..200).map(|_| g.step(&mut strategy).unwrap()).collect::<Vec<_>>()
+    };
+    assert_eq!(play(), play());
+}
@@ -0,0 +1,134 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, Color, Deck, GameRules, ParseCardError, Suit};
+use rand::SeedableRng;
+
+#[test]
+fn parse_round_trips_display() {
+    let mut cards = vec![Card::Joker(0), Card::Joker(2)];
+    for &suit in Suit::all().iter() {
+        for num in 1..14 {
+            cards.push(Card::new(suit, num));
+        }
+    }
+    for card in cards {
+        assert_eq!(card.to_string().parse::<Card>(), Ok(card));
+    }
+    assert_eq!("\u{2660}J".parse::<Card>(), Ok(Card::Special(Suit::Spades, 11)));
+    assert_eq!("\u{2666}Q".parse::<Card>(), Ok(Card::Special(Suit::Diamonds, 12)));
+}
+
+#[test]
+fn parse_rejects_malformed_cards() {
+    assert_eq!("".parse::<Card>(), Err(ParseCardError::Empty));
+    assert_eq!("xA".parse::<Card>(), Err(ParseCardError::UnknownSuit('x')));
+    assert_eq!("\u{2660}1".parse::<Card>(), Err(ParseCardError::OutOfRange("1".to_owned())));
+    assert_eq!("\u{2660}10".parse::<Card>(), Err(ParseCardError::InvalidRank("10".to_owned())));
+    assert_eq!("J".parse::<Card>(), Err(ParseCardError::InvalidRank("J".to_owned())));
+    assert_eq!("J300".parse::<Card>(), Err(ParseCardError::OutOfRange("300".to_owned())));
+}
+
+#[test]
+fn suit_char_round_trip() {
+    for &(suit, letter) in &[(Suit::Spades, 'S'), (Suit::Hearts, 'H'),
+                             (Suit::Clubs, 'C'), (Suit::Diamonds, 'D')] {
+        assert_eq!(Suit::from_char(suit.to_char()), Some(suit));
+        assert_eq!(Suit::from_char(letter), Some(suit));
+        assert_eq!(suit.to_string(), suit.to_char().to_string());
+    }
+    assert_eq!(Suit::from_char('x'), None);
+}
+
+#[test]
+fn deck_uses_configured_specials() {
+    let rules = GameRules { specials: vec![(Suit::Hearts, 7), (Suit::Clubs, 1)], ..GameRules::default() };
+    let deck = Deck::with_rules(1, 0, &rules);
+    let specials: Vec<Card> = deck.iter().cloned().filter(|c| matches!(*c, Card::Special(..))).collect();
+    assert_eq!(specials, vec![Card::Special(Suit::Hearts, 7), Card::Special(Suit::Clubs, 1)]);
+    assert!(deck.iter().any(|&c| c == Card::Regular(Suit::Diamonds, 12)));
+}
+
+#[test]
+fn sorting_a_shuffled_deck() {
+    let mut deck = Deck::new(3);
+    deck.shuffle(&mut rand::prng::XorShiftRng::from_seed([1; 16]));
+    let mut cards: Vec<Card> = deck.iter().cloned().collect();
+    cards.sort();
+    let mut expected = Vec::new();
+    for &suit in Suit::all().iter() {
+        for num in 1..14 {
+            expected.push(Card::new(suit, num));
+        }
+    }
+    expected.extend_from_slice(&[Card::Joker(0), Card::Joker(1), Card::Joker(2)]);
+    assert_eq!(cards, expected);
+    assert!(Card::Regular(Suit::Spades, 11) < Card::Special(Suit::Spades, 11));
+}
+
+#[test]
+fn two_decks_have_104_suited_cards() {
+    let deck = Deck::with_decks(2, 3);
+    assert_eq!(deck.len(), 107);
+    assert_eq!(deck.iter().filter(|c| c.suit().is_some()).count(), 104);
+    assert_eq!(deck.iter().filter(|c| matches!(**c, Card::Special(..))).count(), 4);
+}
+
+#[test]
+fn only_regular_red_suits_are_red() {
+    for &suit in Suit::all().iter() {
+        assert_eq!(Card::Regular(suit, 5).is_red(), suit.is_red());
+        assert!(!Card::Special(suit, 12).is_red());
+    }
+    assert!(Card::Regular(Suit::Hearts, 1).is_red());
+    assert!(!Card::Regular(Suit::Clubs, 1).is_red());
+    assert!(!Card::Joker(0).is_red());
+}
+
+#[test]
+fn all_suits_are_sorted_and_round_trip_through_chars() {
+    let suits = Suit::all();
+    assert!(suits.windows(2).all(|w| w[0] < w[1]));
+    for &suit in suits.iter() {
+        assert_eq!(Suit::from_char(suit.to_char()), Some(suit));
+    }
+}
+
+#[test]
+fn color_follows_the_suit_and_jokers_have_none() {
+    assert_eq!(Card::new(Suit::Hearts, 3).color(), Some(Color::Red));
+    assert_eq!(Card::new(Suit::Clubs, 3).color(), Some(Color::Black));
+    assert_eq!(Card::Special(Suit::Diamonds, 12).color(), Some(Color::Red));
+    assert_eq!(Card::Joker(0).color(), None);
+    for &suit in Suit::all().iter() {
+        assert_eq!(suit.color() == Color::Red, suit.is_red());
+    }
+}
+
+#[test]
+fn render_wraps_red_cards_in_ansi_codes() {
+    assert_eq!(Card::new(Suit::Hearts, 10).render(true), "\x1b[31m\u{2665}T\x1b[0m");
+    assert_eq!(Card::new(Suit::Diamonds, 12).render(true), "\x1b[31m\u{2666}Q\x1b[0m");
+    assert_eq!(Card::new(Suit::Spades, 1).render(true), "\u{2660}A");
+    assert_eq!(Card::Joker(0).render(true), "J0");
+    assert_eq!(Card::new(Suit::Hearts, 10).render(false), "\u{2665}T");
+    assert_eq!(Card::new(Suit::Diamonds, 12).render(false), "\u{2666}Q");
+}
+
+#[test]
+fn indices_are_dense_and_round_trip() {
+    let mut deck: Vec<Card> = Deck::new(3).into_cards();
+    deck.sort();
+    let indices: Vec<usize> = deck.iter().map(|c| c.to_index().unwrap()).collect();
+    assert_eq!(indices, (0..55).collect::<Vec<usize>>());
+    for &card in deck.iter() {
+        assert_eq!(card.to_index().and_then(Card::from_index), Some(card));
+    }
+    assert_eq!(Card::Regular(Suit::Diamonds, 12).to_index(),
+               Card::Special(Suit::Diamonds, 12).to_index());
+    assert_eq!(Card::from_index_with(50, &[]), Some(Card::Regular(Suit::Diamonds, 12)));
+    assert_eq!(Card::from_index(52 + 255), Some(Card::Joker(255)));
+    assert_eq!(Card::from_index(52 + 256), None);
+    assert_eq!(Card::Regular(Suit::Spades, 0).to_index(), None);
+    assert_eq!(Card::Special(Suit::Hearts, 14).to_index(), None);
+}
@@ -0,0 +1,36 @@
+extern crate proptest;
+extern crate prugel;
+extern crate rand;
+
+use proptest::prelude::*;
+use prugel::{Game, GameRules, RandomStrategy, StepOutcome};
+use rand::SeedableRng;
+
+fn card_count(g: &Game) -> usize {
+    g.remaining_cards().len() + g.removed().len() + g.hands().iter().map(|h| h.len()).sum::<usize>()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn cards_are_conserved(players in 2usize..8, decks in 1usize..3, jokers in 0u8..5,
+                           consume in any::<bool>(), seed in any::<[u8; 16]>(),
+                           choices in any::<[u8; 16]>(), steps in 0usize..400) {
+        let rules = GameRules { consume_special_on_win: consume, ..GameRules::default() };
+        let mut g = Game::with_rules(players, decks, jokers, rules);
+        let mut rng = rand::prng::XorShiftRng::from_seed(seed);
+        let mut strategy = RandomStrategy::from_seed(choices);
+        let total = 52 * decks + jokers as usize;
+        g.shuffle_with_rng(&mut rng);
+        prop_assert_eq!(card_count(&g), total);
+        for _ in 0..steps {
+            match g.step_with_rng(&mut rng, &mut strategy).unwrap() {
+                StepOutcome::Played(_) => (),
+                StepOutcome::Exhausted | StepOutcome::Stalemate => break,
+            }
+            prop_assert_eq!(card_count(&g), total);
+            prop_assert_eq!(g.validate(), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, CardError, Deck, GameRules, Suit};
+use rand::SeedableRng;
+
+#[test]
+fn from_cards_pops_the_last_card_first() {
+    let cards = vec![Card::new(Suit::Spades, 2), Card::new(Suit::Hearts, 3), Card::Joker(0)];
+    let mut deck = Deck::from_cards(cards.clone()).unwrap();
+    assert_eq!(deck.clone().into_cards(), cards);
+    assert_eq!(deck.pop(), Some(Card::Joker(0)));
+    assert_eq!(deck.pop(), Some(Card::new(Suit::Hearts, 3)));
+    assert_eq!(deck.pop(), Some(Card::new(Suit::Spades, 2)));
+    assert_eq!(deck.pop(), None);
+}
+
+#[test]
+fn shuffle_with_is_pinned() {
+    let cards: Vec<Card> = (1..9).map(|n| Card::new(Suit::Clubs, n)).collect();
+    let mut deck = Deck::from_cards(cards).unwrap();
+    deck.shuffle_with(&mut rand::prng::XorShiftRng::from_seed([1; 16]));
+    let ranks: Vec<u8> = deck.iter().map(|c| c.rank().unwrap()).collect();
+    assert_eq!(ranks, vec![6, 3, 1, 7, 5, 4, 8, 2]);
+}
+
+#[test]
+fn from_cards_rejects_malformed_cards() {
+    let bad_rank = Card::Regular(Suit::Clubs, 14);
+    assert!(!bad_rank.is_valid());
+    assert_eq!(Deck::from_cards(vec![Card::Joker(0), bad_rank]),
+               Err(CardError::RankOutOfRange(14)));
+    let queen = Card::Regular(Suit::Diamonds, 12);
+    assert!(!queen.is_valid());
+    assert_eq!(Deck::from_cards(vec![queen]), Err(CardError::SpecialMismatch(queen)));
+    let rules = GameRules { specials: Vec::new(), ..GameRules::default() };
+    assert!(queen.is_valid_with(&rules.specials));
+    assert!(Deck::from_cards_with(vec![queen], &rules).is_ok());
+    let special = Card::Special(Suit::Diamonds, 12);
+    assert_eq!(Deck::from_cards_with(vec![special], &rules),
+               Err(CardError::SpecialMismatch(special)));
+    assert_eq!(Deck::from_cards_unchecked(vec![bad_rank]).len(), 1);
+}
+
+#[test]
+fn draw_bottom_takes_the_card_drawn_last() {
+    let cards = vec![Card::new(Suit::Spades, 2), Card::new(Suit::Hearts, 3), Card::Joker(0)];
+    let mut deck = Deck::from_cards(cards).unwrap();
+    assert_eq!(deck.draw_bottom(), Some(Card::new(Suit::Spades, 2)));
+    assert_eq!(deck.draw_top(), Some(Card::Joker(0)));
+    assert_eq!(deck.draw_bottom(), Some(Card::new(Suit::Hearts, 3)));
+    assert!(deck.is_empty());
+    assert_eq!(deck.draw_bottom(), None);
+}
+
+#[test]
+fn draw_n_returns_the_top_card_first() {
+    let cards = vec![Card::new(Suit::Spades, 2), Card::new(Suit::Hearts, 3), Card::Joker(0)];
+    let mut deck = Deck::from_cards(cards).unwrap();
+    assert_eq!(deck.draw_n(2), vec![Card::Joker(0), Card::new(Suit::Hearts, 3)]);
+    assert_eq!(deck.len(), 1);
+    assert_eq!(deck.draw_n(5), vec![Card::new(Suit::Spades, 2)]);
+    assert_eq!(deck.draw_n(1), Vec::<Card>::new());
+}
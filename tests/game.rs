@@ -0,0 +1,563 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, DealError, FirstEligibleStrategy, Game, GameObserver, GameRules, Hand,
+             NamesError, RandomStrategy, ReceiverPreview, ScriptedStrategy, StepError,
+             StepOutcome, Strategy, Suit, WinCondition};
+use rand::SeedableRng;
+
+#[test]
+fn draining_every_card_exhausts_the_game() {
+    // Jokers and special cards always end up back in the discard pile, but
+    // otherwise enough players can hold every card at once.
+    let rules = GameRules { specials: vec![], ..GameRules::default() };
+    for seed in 1..6 {
+        let mut g = Game::with_rules(40, 1, 0, rules.clone());
+        let mut rng = rand::prng::XorShiftRng::from_seed([seed; 16]);
+        let mut strategy = RandomStrategy::from_seed([seed; 16]);
+        g.shuffle_with_rng(&mut rng);
+        let results = g.run(&mut rng, &mut strategy, 10_000).unwrap();
+        assert!(results.len() < 10_000);
+        assert_eq!(g.step_with_rng(&mut rng, &mut strategy).unwrap(), StepOutcome::Exhausted);
+    }
+}
+
+#[test]
+fn draining_with_jokers_and_consumed_specials_ends_the_game() {
+    // Winning specials leave play. A winning joker would always go back to
+    // the discard pile and keep the game alive, so jokers don't win here
+    // and can be held like any other card instead.
+    let rules = GameRules {
+        consume_special_on_win: true,
+        win_precedence: vec![WinCondition::Special, WinCondition::FiveCards,
+                             WinCondition::TwentyFive],
+        ..GameRules::default()
+    };
+    let mut consumed = 0;
+    for seed in 1..6 {
+        let mut g = Game::with_rules(40, 1, 3, rules.clone());
+        let mut rng = rand::prng::XorShiftRng::from_seed([seed; 16]);
+        let mut strategy = RandomStrategy::from_seed([seed; 16]);
+        g.shuffle_with_rng(&mut rng);
+        let results = g.run(&mut rng, &mut strategy, 10_000).unwrap();
+        assert!(results.len() < 10_000);
+        let outcome = g.step_with_rng(&mut rng, &mut strategy).unwrap();
+        assert!(outcome == StepOutcome::Exhausted || outcome == StepOutcome::Stalemate);
+        assert_eq!(g.validate(), Ok(()));
+        assert!(results.iter().any(|r| r.card.is_joker() && r.receiver.is_some()));
+        consumed += g.removed().len();
+    }
+    assert!(consumed > 0);
+}
+
+#[test]
+fn first_eligible_games_are_reproducible() {
+    let play = || {
+        let mut g = Game::new(4, 2);
+        g.shuffle_with_rng(&mut rand::prng::XorShiftRng::from_seed([9; 16]));
+        let mut rng = rand::prng::XorShiftRng::from_seed([0; 16]);
+        g.run(&mut rng, &mut FirstEligibleStrategy, 500).unwrap()
+    };
+    assert_eq!(play(), play());
+}
+
+#[test]
+fn rounds_matches_run() {
+    let mut a = Game::new(4, 2);
+    let mut b = a.clone();
+    let mut rng = rand::prng::XorShiftRng::from_seed([3; 16]);
+    let played: Result<Vec<_>, _> =
+        a.rounds(&mut rng.clone(), &mut FirstEligibleStrategy).take(300).collect();
+    assert_eq!(played, b.run(&mut rng, &mut FirstEligibleStrategy, 300));
+    assert_eq!(a, b);
+}
+
+#[test]
+fn rounds_ends_with_an_invalid_choice() {
+    // The king of diamonds on top of an unshuffled deck may go to anyone,
+    // so a choice of 7 is rejected on the first round.
+    let mut g = Game::new(3, 0);
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    let mut strategy = Stubborn(7);
+    let results: Vec<_> = g.rounds(&mut rng, &mut strategy).collect();
+    assert_eq!(results, vec![Err(StepError::InvalidStrategyChoice { returned: 7,
+                                                                   eligible: vec![0, 1, 2] })]);
+}
+
+#[test]
+fn rounds_stops_when_the_deck_runs_out() {
+    let rules = GameRules { specials: vec![], ..GameRules::default() };
+    let mut g = Game::with_rules(40, 1, 0, rules);
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    g.shuffle_with_rng(&mut rng);
+    let mut strategy = RandomStrategy::from_seed([1; 16]);
+    let n = g.rounds(&mut rng, &mut strategy).take(10_000).count();
+    assert!(n < 10_000);
+    assert_eq!(g.step_with_rng(&mut rng, &mut strategy).unwrap(), StepOutcome::Exhausted);
+}
+
+#[test]
+fn win_probability_counts_winning_draws() {
+    let g = Game::new(2, 0);
+    // Nothing dealt yet: only a special card wins, and both reach the giver.
+    assert_eq!(g.win_probability(0), 2.0 / 52.0);
+    // Player 1 is not the giver, so only red cards reach them, and neither
+    // red ace nor any other red card wins on an empty hand.
+    assert_eq!(g.win_probability(1), 0.0);
+    let with_jokers = Game::new(2, 2);
+    assert_eq!(with_jokers.win_probability(0), 4.0 / 54.0);
+    assert_eq!(Game::with_rules(2, 0, 0, GameRules::default()).win_probability(0), 0.0);
+}
+
+#[test]
+fn rejected_cards_land_on_the_discard_pile() {
+    let mut g = Game::new(3, 2);
+    let mut rng = rand::prng::XorShiftRng::from_seed([4; 16]);
+    g.shuffle_with_rng(&mut rng);
+    assert!(g.discard().is_empty());
+    for _ in 0..40 {
+        let outcome = g.step_with_rng(&mut rng, &mut FirstEligibleStrategy).unwrap();
+        let result = outcome.played().unwrap();
+        if result.receiver.is_none() {
+            assert_eq!(g.discard().peek(), Some(&result.card));
+        }
+    }
+}
+
+#[test]
+fn red_jokers_can_go_to_any_player() {
+    // An unshuffled deck has its last joker on top.
+    let rules = GameRules { joker_is_red: true, ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 1, rules);
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    let outcome = g.step_with_rng(&mut rng, &mut ScriptedStrategy::new(vec![2])).unwrap();
+    let result = outcome.played().unwrap();
+    assert_eq!((result.card, result.giver), (Card::Joker(0), 0));
+    assert_eq!((result.receiver, result.win), (Some(2), Some(WinCondition::Joker)));
+
+    let mut g = Game::with_rules(3, 1, 1, GameRules::default());
+    let outcome = g.step_with_rng(&mut rng, &mut ScriptedStrategy::new(vec![])).unwrap();
+    let result = outcome.played().unwrap();
+    assert_eq!((result.receiver, result.win), (Some(0), Some(WinCondition::Joker)));
+}
+
+#[test]
+fn rollouts_diverge_by_seed() {
+    let mut g = Game::new(3, 2);
+    g.shuffle();
+    let mut strategy = RandomStrategy::from_seed([2; 16]);
+    for _ in 0..10 {
+        g.step(&mut strategy).unwrap();
+    }
+    let rollout = |seed| {
+        let mut r = g.clone_for_rollout(seed);
+        assert!(r.history().is_empty());
+        r.shuffle();
+        let mut strategy = RandomStrategy::from_seed([2; 16]);
+        for _ in 0..50 {
+            r.step(&mut strategy).unwrap();
+        }
+        r
+    };
+    assert_eq!(rollout(1), rollout(1));
+    assert!(rollout(1) != rollout(2));
+    assert_eq!(g.history().len(), 10);
+}
+
+#[test]
+fn preview_step_matches_the_routing_rules() {
+    let g = Game::new(3, 1);
+    let red = g.preview_step(Card::new(Suit::Hearts, 4));
+    assert_eq!(red, ReceiverPreview { giver: 0, red: true, eligible: vec![0, 1, 2], forced: None });
+    let black = g.preview_step(Card::new(Suit::Clubs, 4));
+    assert_eq!((black.red, black.eligible, black.forced), (false, vec![0], Some(0)));
+    assert_eq!(g.preview_step(Card::Joker(0)).forced, Some(0));
+    assert_eq!(g, Game::new(3, 1));
+}
+
+#[test]
+fn the_giver_rotates_every_round() {
+    let mut g = Game::new(3, 0);
+    g.shuffle();
+    for round in 0..7 {
+        assert_eq!((g.round(), g.current_giver()), (round, round % 3));
+        let result = g.step(&mut FirstEligibleStrategy).unwrap().played().unwrap();
+        assert_eq!(result.giver, round % 3);
+    }
+}
+
+#[test]
+fn hands_are_readable_by_index() {
+    let mut g = Game::new(4, 0);
+    g.shuffle();
+    let mut rng = rand::prng::XorShiftRng::from_seed([6; 16]);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 20).unwrap();
+    assert_eq!(g.num_players(), 4);
+    assert_eq!(g.hands().len(), 4);
+    assert_eq!(g.hand(2), Some(&g.hands()[2]));
+    assert_eq!(g.hand(4), None);
+}
+
+#[test]
+fn equality_depends_on_deck_order_but_not_the_rng() {
+    let g = Game::new(3, 1);
+    let mut reseeded = g.clone();
+    reseeded.set_rng(rand::prng::XorShiftRng::from_seed([8; 16]));
+    assert_eq!(reseeded, g);
+    let mut shuffled = g.clone();
+    shuffled.shuffle();
+    assert!(shuffled != g);
+}
+
+#[test]
+fn position_key_ignores_hidden_order() {
+    let mut g = Game::new(3, 1);
+    g.shuffle();
+    let mut rng = rand::prng::XorShiftRng::from_seed([2; 16]);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 30).unwrap();
+    let key = g.position_key();
+    let mut other = g.clone();
+    other.shuffle_with_rng(&mut rand::prng::XorShiftRng::from_seed([9; 16]));
+    assert!(other != g);
+    assert_eq!(other.position_key(), key);
+    other.step(&mut FirstEligibleStrategy).unwrap();
+    assert!(other.position_key() != key);
+}
+
+#[test]
+fn scores_follow_the_rules_points() {
+    let rules = GameRules { win_points: vec![(WinCondition::Joker, 10)], ..GameRules::default() };
+    assert_eq!(rules.points(WinCondition::Joker), 10);
+    assert_eq!(rules.points(WinCondition::FiveCards), WinCondition::FiveCards.points());
+    let mut g = Game::with_rules(3, 1, 2, rules.clone());
+    g.shuffle();
+    let mut rng = rand::prng::XorShiftRng::from_seed([5; 16]);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 300).unwrap();
+    let mut expected = [0; 3];
+    for result in g.history() {
+        if let (Some(i), Some(w)) = (result.receiver, result.win) {
+            expected[i] += rules.points(w);
+        }
+    }
+    assert!(expected.iter().sum::<u32>() > 0);
+    assert_eq!(g.scores(), &expected);
+}
+
+#[test]
+fn a_match_ends_once_someone_reaches_the_target() {
+    let mut g = Game::new(3, 2);
+    let mut rng = rand::prng::XorShiftRng::from_seed([12; 16]);
+    g.shuffle_with_rng(&mut rng);
+    let winner = g.play_match(&mut rng, &mut RandomStrategy::from_seed([12; 16]), 10).unwrap();
+    let winner = winner.unwrap();
+    assert!(g.scores()[winner] >= 10);
+    assert!(g.scores().iter().enumerate().all(|(i, &s)| i == winner || s < 10));
+    assert!(g.wins().iter().sum::<u32>() >= 4);
+}
+
+#[test]
+fn remaining_cards_complement_the_hands() {
+    let mut g = Game::new(4, 2);
+    g.shuffle();
+    let mut rng = rand::prng::XorShiftRng::from_seed([3; 16]);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 70).unwrap();
+    let mut all = g.remaining_cards();
+    for hand in g.hands() {
+        all.extend_from_slice(hand.cards());
+    }
+    all.sort();
+    let mut fresh: Vec<Card> = prugel::Deck::new(2).into_cards();
+    fresh.sort();
+    assert_eq!(all, fresh);
+}
+
+#[test]
+fn consumed_specials_never_return() {
+    let rules = GameRules { consume_special_on_win: true, ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 1, rules);
+    let mut rng = rand::prng::XorShiftRng::from_seed([10; 16]);
+    g.shuffle_with_rng(&mut rng);
+    let results = g.run(&mut rng, &mut RandomStrategy::from_seed([10; 16]), 2000).unwrap();
+    let mut consumed = Vec::new();
+    for result in results.iter() {
+        assert!(!consumed.contains(&result.card));
+        if result.card.is_special() && result.receiver.is_some() {
+            consumed.push(result.card);
+        }
+    }
+    assert!(!consumed.is_empty());
+    consumed.sort();
+    let mut removed = g.removed().clone().into_cards();
+    removed.sort();
+    assert_eq!(removed, consumed);
+    assert!(g.remaining_cards().iter().all(|c| !consumed.contains(c)));
+}
+
+#[test]
+fn games_stay_valid_while_playing() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([11; 16]);
+    let mut strategy = RandomStrategy::from_seed([60; 16]);
+    let rules = GameRules { consume_special_on_win: true, ..GameRules::default() };
+    let mut g = Game::with_rules(4, 2, 3, rules);
+    g.shuffle_with_rng(&mut rng);
+    assert_eq!(g.validate(), Ok(()));
+    for _ in 0..500 {
+        g.step_with_rng(&mut rng, &mut strategy).unwrap();
+        assert_eq!(g.validate(), Ok(()));
+    }
+    while g.undo().is_some() {
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn restoring_a_snapshot_replays_the_same_rounds() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([12; 16]);
+    let mut g = Game::new(4, 2);
+    g.shuffle_with_rng(&mut rng);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 30).unwrap();
+    let snap = g.to_snapshot();
+    let mut again = rng.clone();
+    let first = g.run(&mut rng, &mut FirstEligibleStrategy, 80).unwrap();
+    g.restore(snap.clone());
+    assert_eq!(g.round(), snap.round());
+    assert!(g.history().is_empty());
+    assert_eq!(g.to_snapshot(), snap);
+    assert_eq!(g.validate(), Ok(()));
+    assert_eq!(g.run(&mut again, &mut FirstEligibleStrategy, 80).unwrap(), first);
+}
+
+#[test]
+fn remaining_counts_bucket_every_card() {
+    let mut g = Game::new(4, 2);
+    assert_eq!(g.remaining_by_rank(), [2, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 3, 3, 4]);
+    assert_eq!(g.remaining_by_suit(), [13; 4]);
+    let mut rng = rand::prng::XorShiftRng::from_seed([4; 16]);
+    g.shuffle_with_rng(&mut rng);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 25).unwrap();
+    let remaining = g.remaining_cards();
+    let by_rank = g.remaining_by_rank();
+    for n in 1..14 {
+        let count = remaining.iter().filter(|c| c.is_regular() && c.rank() == Some(n)).count();
+        assert_eq!(by_rank[n as usize] as usize, count);
+    }
+    assert_eq!(by_rank[0] as usize, remaining.iter().filter(|c| c.is_joker()).count());
+    for (i, &suit) in Suit::all().iter().enumerate() {
+        let count = remaining.iter().filter(|c| c.suit() == Some(suit)).count();
+        assert_eq!(g.remaining_by_suit()[i] as usize, count);
+    }
+}
+
+#[test]
+fn legal_receivers_follow_colour_and_hand_sums() {
+    // Dealing from the top of an unshuffled deck gives sums of 23, 21 and
+    // 19 (king and ten, queen and nine, jack and eight of diamonds).
+    let rules = GameRules { specials: Vec::new(), ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 0, rules);
+    g.deal(2).unwrap();
+    assert_eq!(g.legal_receivers(Card::new(Suit::Clubs, 2)), vec![0]);
+    assert_eq!(g.legal_receivers(Card::new(Suit::Clubs, 5)), Vec::<usize>::new());
+    assert_eq!(g.legal_receivers(Card::new(Suit::Hearts, 4)), vec![1, 2]);
+    assert_eq!(g.legal_receivers(Card::new(Suit::Hearts, 2)), vec![0, 1, 2]);
+    assert_eq!(g.preview_step(Card::new(Suit::Hearts, 4)).eligible, vec![1, 2]);
+}
+
+#[test]
+fn deal_keeps_the_reserved_hand_capacity() {
+    let rules = GameRules { specials: Vec::new(), ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 0, rules);
+    g.deal(2).unwrap();
+    assert!(g.hands().iter().all(|h| h.len() == 2 && h.capacity() >= 5));
+}
+
+#[test]
+fn rejected_deals_leave_the_game_untouched() {
+    // From the top, the unshuffled deck is the king, queen, jack, ten and
+    // nine of diamonds; with no specials the queen is a plain card.
+    let rules = GameRules { specials: Vec::new(), ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 0, rules.clone());
+    let before = g.clone();
+    assert_eq!(g.deal(20), Err(DealError::NotEnoughCards { needed: 60, available: 52 }));
+    assert_eq!(g, before);
+    let mut g = Game::with_rules(2, 1, 0, rules.clone());
+    let before = g.clone();
+    assert_eq!(g.deal(3), Err(DealError::Bust { player: 0, card: Card::new(Suit::Diamonds, 9) }));
+    assert_eq!(g, before);
+    let mut g = Game::with_rules(1, 1, 0, rules);
+    let before = g.clone();
+    assert_eq!(g.deal(2),
+               Err(DealError::Win { player: 0, card: Card::Regular(Suit::Diamonds, 12) }));
+    assert_eq!(g, before);
+}
+
+#[test]
+fn remaining_counts_saturate_for_many_decks() {
+    let g = Game::with_rules(2, 20, 0, GameRules::default());
+    assert_eq!(g.remaining_by_suit(), [255; 4]);
+    assert_eq!(g.remaining_by_rank()[1], 80);
+}
+
+#[test]
+fn unreachable_five_card_wins_do_not_reserve_huge_hands() {
+    let rules = GameRules { cards_to_win: usize::MAX, ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 0, rules);
+    g.shuffle();
+    let mut rng = rand::prng::XorShiftRng::from_seed([2; 16]);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 50).unwrap();
+    assert_eq!(g.validate(), Ok(()));
+}
+
+#[test]
+fn stalemate_after_exactly_the_idle_limit() {
+    assert_eq!(GameRules::default().stalemate_rounds, Some(1000));
+    // With a target of zero and no special cards, nobody can take anything.
+    let rules = GameRules {
+        target_sum: 0,
+        specials: Vec::new(),
+        stalemate_rounds: Some(3),
+        ..GameRules::default()
+    };
+    let mut g = Game::with_rules(3, 1, 0, rules.clone());
+    let mut rng = rand::prng::XorShiftRng::from_seed([6; 16]);
+    g.shuffle_with_rng(&mut rng);
+    for _ in 0..3 {
+        assert!(!g.is_stalemate());
+        let result = g.step_with_rng(&mut rng, &mut FirstEligibleStrategy).unwrap().played();
+        assert_eq!(result.unwrap().receiver, None);
+    }
+    assert!(g.is_stalemate());
+    let before = g.clone();
+    assert_eq!(g.step_with_rng(&mut rng, &mut FirstEligibleStrategy), Ok(StepOutcome::Stalemate));
+    assert_eq!(g, before);
+
+    let mut g = Game::with_rules(3, 1, 0, GameRules { stalemate_rounds: None, ..rules });
+    g.shuffle_with_rng(&mut rng);
+    for _ in 0..500 {
+        let outcome = g.step_with_rng(&mut rng, &mut FirstEligibleStrategy).unwrap();
+        assert!(outcome.played().is_some());
+        assert!(!g.is_stalemate());
+    }
+}
+
+#[test]
+fn names_must_match_the_players_and_show_in_describe() {
+    let mut g = Game::new(3, 0);
+    let err = g.set_names(vec!["Ann".to_owned(), "Bob".to_owned()]).unwrap_err();
+    assert_eq!(err, NamesError { players: 3, names: 2 });
+    assert_eq!(g.names(), None);
+    assert_eq!(g.player_name(1), "1");
+
+    g.set_names(vec!["Ann".to_owned(), "Bob".to_owned(), "Cy".to_owned()]).unwrap();
+    assert_eq!(g.player_name(2), "Cy");
+    assert_eq!(g.player_name(5), "5");
+    // The top card of an unshuffled deck is the king of diamonds.
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    let result = g.step_with_rng(&mut rng, &mut FirstEligibleStrategy).unwrap().played().unwrap();
+    assert_eq!(result.describe(&g), "Ann \u{2666}K to Ann => 13");
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Reshuffle,
+    Draw(Card),
+    Accept(usize, Card),
+    Win(usize, WinCondition),
+}
+
+struct Stubborn(usize);
+
+impl Strategy for Stubborn {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, _eligible: &[usize])
+            -> usize {
+        self.0
+    }
+}
+
+struct Recorder(Vec<Event>);
+
+impl GameObserver for Recorder {
+    fn on_reshuffle(&mut self) {
+        self.0.push(Event::Reshuffle);
+    }
+
+    fn on_draw(&mut self, card: Card) {
+        self.0.push(Event::Draw(card));
+    }
+
+    fn on_accept(&mut self, player: usize, card: Card) {
+        self.0.push(Event::Accept(player, card));
+    }
+
+    fn on_win(&mut self, player: usize, condition: WinCondition) {
+        self.0.push(Event::Win(player, condition));
+    }
+}
+
+#[test]
+fn observers_see_each_round_in_order() {
+    let mut g = Game::new(3, 1);
+    let mut rng = rand::prng::XorShiftRng::from_seed([13; 16]);
+    g.shuffle_with_rng(&mut rng);
+    let mut strategy = RandomStrategy::from_seed([13; 16]);
+    let mut recorder = Recorder(Vec::new());
+    for _ in 0..300 {
+        g.step_observed(&mut rng, &mut strategy, &mut recorder).unwrap();
+    }
+    let mut expected = Vec::new();
+    for r in g.history() {
+        if r.reshuffled {
+            expected.push(Event::Reshuffle);
+        }
+        expected.push(Event::Draw(r.card));
+        if let Some(i) = r.receiver {
+            expected.push(Event::Accept(i, r.card));
+            if let Some(w) = r.win {
+                expected.push(Event::Win(i, w));
+            }
+        }
+    }
+    assert!(g.history().iter().any(|r| r.reshuffled));
+    assert!(g.history().iter().any(|r| r.win.is_some()));
+    assert_eq!(recorder.0, expected);
+}
+
+#[test]
+fn specials_only_win_when_the_rules_list_them() {
+    let rules = GameRules {
+        win_precedence: vec![WinCondition::FiveCards, WinCondition::TwentyFive],
+        ..GameRules::default()
+    };
+    assert_eq!(Game::new(3, 0).win_probability(0), 2.0 / 52.0);
+    assert_eq!(Game::with_rules(3, 1, 0, rules.clone()).win_probability(0), 0.0);
+
+    let mut g = Game::with_rules(3, 1, 0, rules);
+    let mut rng = rand::prng::XorShiftRng::from_seed([14; 16]);
+    g.shuffle_with_rng(&mut rng);
+    let results = g.run(&mut rng, &mut RandomStrategy::from_seed([14; 16]), 300).unwrap();
+    assert!(results.iter().all(|r| r.win != Some(WinCondition::Special)));
+    assert!(results.iter().any(|r| r.card.is_special() && r.receiver.is_some()));
+    assert_eq!(g.validate(), Ok(()));
+    // Undoing as far as the last reshuffle takes held specials back out.
+    while g.undo().is_some() {
+        assert_eq!(g.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn render_board_marks_the_giver() {
+    let rules = GameRules { specials: Vec::new(), ..GameRules::default() };
+    let mut g = Game::with_rules(3, 1, 0, rules);
+    g.deal(2).unwrap();
+    assert_eq!(g.render_board(false),
+               "Round 0, deck 46, discard 0\n\
+                > 0: \u{2666}K \u{2666}T => 23\n  \
+                  1: \u{2666}Q \u{2666}9 => 21\n  \
+                  2: \u{2666}J \u{2666}8 => 19\n");
+    // Nobody can take the seven of diamonds, so it is discarded and the
+    // turn passes to player 1.
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    g.step_with_rng(&mut rng, &mut FirstEligibleStrategy).unwrap();
+    let board = g.render_board(true);
+    assert!(board.starts_with("Round 1, deck 45, discard 1\n  0: "));
+    assert!(board.contains("\n> 1: \x1b[31m\u{2666}Q\x1b[0m \x1b[31m\u{2666}9\x1b[0m => 21\n"));
+}
@@ -0,0 +1,33 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{FirstEligibleStrategy, Game, RoundResult};
+use rand::SeedableRng;
+
+/// Plays the game whose transcript is checked in as `golden/first_eligible.txt`.
+fn play() -> Vec<RoundResult> {
+    let mut g = Game::new(4, 2);
+    let mut rng = rand::prng::XorShiftRng::from_seed([5; 16]);
+    g.shuffle_with_rng(&mut rng);
+    g.run(&mut rng, &mut FirstEligibleStrategy, 300).unwrap()
+}
+
+#[test]
+fn first_eligible_game_matches_the_transcript() {
+    let expected: Vec<RoundResult> = include_str!("golden/first_eligible.txt")
+        .lines()
+        .map(|line| RoundResult::from_record(line).unwrap())
+        .collect();
+    let played = play();
+    // Run with PRUGEL_BLESS set to rewrite the transcript after an
+    // intended change of behaviour.
+    if std::env::var_os("PRUGEL_BLESS").is_some() {
+        let lines: Vec<String> = played.iter().map(RoundResult::to_record).collect();
+        std::fs::write("tests/golden/first_eligible.txt", lines.join("\n") + "\n").unwrap();
+        return;
+    }
+    assert_eq!(played.len(), expected.len());
+    for (i, (p, e)) in played.iter().zip(expected.iter()).enumerate() {
+        assert_eq!(p, e, "round {} differs", i);
+    }
+}
@@ -0,0 +1,263 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, GameRules, Hand, HandError, HandSum, PrugelError, Suit, WinCondition};
+use rand::{Rng, SeedableRng};
+
+#[test]
+fn cached_sum_matches_recomputation() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([5; 16]);
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+    for _ in 0..1000 {
+        let mut hand = Hand::new();
+        let mut accepted: Vec<u8> = Vec::new();
+        loop {
+            let card = Card::new(*rng.choose(&suits).unwrap(), rng.gen_range(1, 14));
+            let n = match card {
+                Card::Regular(_, n) => n,
+                _ => continue,
+            };
+            if !hand.can_accept(card) {
+                break;
+            }
+            hand.accept(card);
+            accepted.push(n);
+            let sum: u8 = accepted.iter().sum();
+            let aces = accepted.contains(&1);
+            match hand.hand_sum() {
+                HandSum::NoWin(s) => assert_eq!(s, sum),
+                HandSum::Win(_) => {
+                    assert!(accepted.len() == 5 || sum == 25 || (sum == 12 && aces));
+                    break;
+                },
+            }
+        }
+    }
+}
+
+#[test]
+fn win_precedence_breaks_ties() {
+    let mut hand = Hand::new();
+    for &suit in &[Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds, Suit::Spades] {
+        hand.accept(Card::new(suit, 5));
+    }
+    assert_eq!(hand.hand_sum(), HandSum::Win(WinCondition::FiveCards));
+    let rules = GameRules {
+        win_precedence: vec![WinCondition::TwentyFive, WinCondition::FiveCards],
+        ..GameRules::default()
+    };
+    assert_eq!(hand.hand_sum_with(&rules), HandSum::Win(WinCondition::TwentyFive));
+}
+
+fn hand(cards: &[(Suit, u8)]) -> Hand {
+    let mut hand = Hand::new();
+    for &(suit, n) in cards {
+        hand.accept(Card::new(suit, n));
+    }
+    hand
+}
+
+#[test]
+fn twelve_with_an_ace_wins() {
+    let with_ace = hand(&[(Suit::Hearts, 1), (Suit::Clubs, 4), (Suit::Clubs, 7)]);
+    assert!(with_ace.is_ace_special());
+    assert_eq!(with_ace.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+
+    let without_ace = hand(&[(Suit::Hearts, 5), (Suit::Clubs, 7)]);
+    assert!(!without_ace.is_ace_special());
+    assert_eq!(without_ace.hand_sum(), HandSum::NoWin(12));
+
+    let two_aces = hand(&[(Suit::Hearts, 1), (Suit::Clubs, 1), (Suit::Clubs, 10)]);
+    assert!(two_aces.is_ace_special());
+    assert_eq!(two_aces.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+
+    let eleven_with_ace = hand(&[(Suit::Hearts, 1), (Suit::Clubs, 10)]);
+    assert!(!eleven_with_ace.is_ace_special());
+    assert_eq!(eleven_with_ace.hand_sum(), HandSum::NoWin(11));
+}
+
+fn cards(s: &str) -> Vec<Card> {
+    s.split(' ').map(|c| c.parse().unwrap()).collect()
+}
+
+#[test]
+fn from_cards_accepts_a_hand_that_already_wins() {
+    let ace = Hand::from_cards(&cards("\u{2660}A \u{2665}9 \u{2663}2")).unwrap();
+    assert_eq!(ace.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+    let hand = Hand::from_cards(&cards("\u{2660}K \u{2665}9 \u{2663}3")).unwrap();
+    assert_eq!(hand.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+}
+
+#[test]
+fn from_cards_rejects_illegal_hands() {
+    assert_eq!(Hand::from_cards(&cards("\u{2660}K \u{2665}K")),
+               Err(HandError::Bust(Card::Regular(Suit::Hearts, 13))));
+    assert_eq!(Hand::from_cards(&cards("J0 \u{2663}2")),
+               Err(HandError::AlreadyWon(Card::Regular(Suit::Clubs, 2))));
+    let bust = Hand::from_cards_unchecked(&cards("\u{2660}K \u{2665}K"));
+    assert_eq!(bust.hand_sum(), HandSum::NoWin(26));
+}
+
+fn clubs(ranks: &[u8]) -> Hand {
+    let cards: Vec<Card> = ranks.iter().map(|&n| Card::new(Suit::Clubs, n)).collect();
+    Hand::from_cards_unchecked(&cards)
+}
+
+#[test]
+fn distance_to_win_takes_the_shortest_path() {
+    let rules = GameRules::default();
+    assert_eq!(Hand::new().distance_to_win(&rules), Some(12));
+    assert_eq!(clubs(&[10, 10]).distance_to_win(&rules), Some(5));
+    assert_eq!(clubs(&[5, 4]).distance_to_win(&rules), Some(3));
+    assert_eq!(clubs(&[2, 2, 2, 2]).distance_to_win(&rules), Some(1));
+    assert_eq!(clubs(&[10, 10, 5]).distance_to_win(&rules), Some(0));
+    let no_ace = GameRules { ace_bonus_sum: 0, win_precedence: vec![WinCondition::TwentyFive],
+                             ..GameRules::default() };
+    assert_eq!(clubs(&[2, 2, 2, 2]).distance_to_win(&no_ace), Some(17));
+    let jokers_only = GameRules { win_precedence: vec![WinCondition::Joker],
+                                  ..GameRules::default() };
+    assert_eq!(clubs(&[2]).distance_to_win(&jokers_only), None);
+    let high_bonus = GameRules { target_sum: 21, ace_bonus_sum: 23, ..GameRules::default() };
+    assert_eq!(Hand::new().distance_to_win(&high_bonus), Some(21));
+    assert_eq!(clubs(&[10, 8]).distance_to_win(&high_bonus), Some(3));
+}
+
+#[test]
+fn accepting_cards_stops_at_the_target() {
+    let rules = GameRules::default();
+    assert_eq!(Hand::new().accepting_cards(&rules), (1..14).collect::<Vec<u8>>());
+    assert_eq!(clubs(&[10, 10]).accepting_cards(&rules), vec![1, 2, 3, 4, 5]);
+    assert_eq!(clubs(&[10, 10, 5]).accepting_cards(&rules), Vec::<u8>::new());
+}
+
+#[test]
+fn len_counts_every_kind_of_card() {
+    let mut hand = Hand::new();
+    assert!(hand.is_empty());
+    hand.accept(Card::new(Suit::Clubs, 3));
+    hand.accept(Card::new(Suit::Spades, 11));
+    assert_eq!(hand.len(), 2);
+    assert!(!hand.is_empty());
+    assert_eq!(hand.cards(), &[Card::Regular(Suit::Clubs, 3), Card::Special(Suit::Spades, 11)]);
+}
+
+#[test]
+fn try_can_accept_rejects_a_winning_hand() {
+    let rules = GameRules::default();
+    let card = Card::new(Suit::Clubs, 1);
+    assert_eq!(clubs(&[10, 10]).try_can_accept_with(card, &rules), Ok(true));
+    assert_eq!(clubs(&[10, 10, 5]).try_can_accept_with(card, &rules),
+               Err(PrugelError::WinningHand));
+}
+
+#[test]
+fn four_card_variant_wins_at_four() {
+    let rules = GameRules { cards_to_win: 4, ..GameRules::default() };
+    let three = clubs(&[2, 2, 2]);
+    assert_eq!(three.hand_sum_with(&rules), HandSum::NoWin(6));
+    assert_eq!(three.distance_to_win(&rules), Some(1));
+    let four = Hand::from_cards_with(&[Card::new(Suit::Clubs, 2); 4], &rules).unwrap();
+    assert_eq!(four.hand_sum_with(&rules), HandSum::Win(WinCondition::FiveCards));
+    assert_eq!(four.hand_sum(), HandSum::NoWin(8));
+    assert_eq!(Hand::from_cards_with(&[Card::new(Suit::Clubs, 2); 5], &rules),
+               Err(HandError::AlreadyWon(Card::new(Suit::Clubs, 2))));
+}
+
+#[test]
+fn winning_hands_accept_nothing() {
+    let rules = GameRules::default();
+    let five = clubs(&[1, 2, 2, 3, 3]);
+    assert_eq!(five.hand_sum(), HandSum::Win(WinCondition::FiveCards));
+    let twenty_five = clubs(&[10, 10, 5]);
+    assert_eq!(twenty_five.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+    for hand in &[five, twenty_five] {
+        assert!(!hand.can_accept(Card::new(Suit::Clubs, 1)));
+        assert!(!hand.can_accept(Card::Joker(0)));
+        assert!(!hand.can_accept_with(Card::new(Suit::Diamonds, 12), &rules));
+    }
+}
+
+#[test]
+fn with_capacity_is_an_empty_hand() {
+    let hand = Hand::with_capacity(5);
+    assert!(hand.is_empty());
+    assert_eq!(hand, Hand::new());
+}
+
+#[test]
+fn each_win_condition_on_its_own() {
+    let joker = Hand::from_cards(&cards("\u{2660}3 J1")).unwrap();
+    assert_eq!(joker.hand_sum(), HandSum::Win(WinCondition::Joker));
+    let special = [Card::new(Suit::Clubs, 4), Card::Special(Suit::Diamonds, 12)];
+    assert_eq!(Hand::from_cards(&special).unwrap().hand_sum(), HandSum::Win(WinCondition::Special));
+    let five = cards("\u{2660}2 \u{2665}3 \u{2663}4 \u{2666}2 \u{2660}3");
+    assert_eq!(Hand::from_cards(&five).unwrap().hand_sum(), HandSum::Win(WinCondition::FiveCards));
+    let target = Hand::from_cards(&cards("\u{2660}K \u{2665}T \u{2663}2")).unwrap();
+    assert_eq!(target.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+    let ace = Hand::from_cards(&cards("\u{2660}A \u{2665}8 \u{2663}3")).unwrap();
+    assert_eq!(ace.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+    let none = Hand::from_cards(&cards("\u{2660}K \u{2665}T \u{2663}A")).unwrap();
+    assert_eq!(none.hand_sum(), HandSum::NoWin(24));
+    assert_eq!(Hand::new().hand_sum(), HandSum::NoWin(0));
+}
+
+#[test]
+fn default_precedence_is_joker_special_five_cards_target() {
+    let all = Hand::from_cards_unchecked(&cards("\u{2660}5 \u{2665}5 \u{2663}5 \u{2666}5 J0"));
+    assert_eq!(all.hand_sum(), HandSum::Win(WinCondition::Joker));
+    let mut with_special = cards("\u{2660}5 \u{2665}5 \u{2663}5 \u{2666}5");
+    with_special.push(Card::Special(Suit::Spades, 11));
+    let special = Hand::from_cards_unchecked(&with_special);
+    assert_eq!(special.hand_sum(), HandSum::Win(WinCondition::Special));
+    with_special.push(Card::Joker(0));
+    let both = Hand::from_cards_unchecked(&with_special);
+    assert_eq!(both.hand_sum(), HandSum::Win(WinCondition::Joker));
+    let five = cards("\u{2660}5 \u{2665}5 \u{2663}5 \u{2666}5 \u{2660}5");
+    assert_eq!(Hand::from_cards_unchecked(&five).hand_sum(), HandSum::Win(WinCondition::FiveCards));
+}
+
+#[test]
+fn huge_sums_and_targets_do_not_overflow() {
+    let rules = GameRules { target_sum: 250, win_precedence: Vec::new(), ..GameRules::default() };
+    let kings = Hand::from_cards_unchecked(&[Card::new(Suit::Clubs, 13); 25]);
+    assert_eq!(kings.hand_sum_with(&rules), HandSum::NoWin(255));
+    assert!(!kings.can_accept_with(Card::new(Suit::Hearts, 2), &rules));
+    let mut hand = Hand::from_cards_unchecked(&[Card::new(Suit::Clubs, 13); 18]);
+    hand.accept_with(Card::new(Suit::Clubs, 11), &rules);
+    assert_eq!(hand.hand_sum_with(&rules), HandSum::NoWin(245));
+    assert!(hand.can_accept_with(Card::new(Suit::Hearts, 5), &rules));
+    assert!(!hand.can_accept_with(Card::new(Suit::Hearts, 13), &rules));
+    assert_eq!(hand.accepting_cards(&rules), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn hands_display_their_cards_and_sum() {
+    let cards = [Card::new(Suit::Spades, 1), Card::new(Suit::Hearts, 10),
+                 Card::new(Suit::Clubs, 3)];
+    assert_eq!(Hand::from_cards_unchecked(&cards).to_string(),
+               "\u{2660}A \u{2665}T \u{2663}3 => 14");
+    assert_eq!(clubs(&[10, 10, 5]).to_string(), "\u{2663}T \u{2663}T \u{2663}5 => TwentyFive");
+    assert_eq!(Hand::new().to_string(), "=> 0");
+}
+
+#[test]
+fn removing_from_a_winning_hand_keeps_the_sum_in_sync() {
+    let mut hand = clubs(&[10, 10, 5]);
+    assert_eq!(hand.hand_sum(), HandSum::Win(WinCondition::TwentyFive));
+    assert!(hand.contains(Card::new(Suit::Clubs, 5)));
+    assert!(!hand.contains(Card::new(Suit::Hearts, 5)));
+    assert!(!hand.remove(Card::new(Suit::Hearts, 5)));
+    assert_eq!(hand.len(), 3);
+    assert!(hand.remove(Card::new(Suit::Clubs, 5)));
+    assert!(!hand.contains(Card::new(Suit::Clubs, 5)));
+    assert_eq!((hand.len(), hand.hand_sum()), (2, HandSum::NoWin(20)));
+    // Two aces and a ten reach the ace bonus sum of 12.
+    let mut hand = clubs(&[1, 10, 1]);
+    assert!(hand.is_ace_special());
+    assert!(hand.remove(Card::new(Suit::Clubs, 1)));
+    assert_eq!((hand.len(), hand.hand_sum()), (2, HandSum::NoWin(11)));
+    assert!(hand.remove(Card::new(Suit::Clubs, 1)));
+    assert!(!hand.remove(Card::new(Suit::Clubs, 1)));
+    assert_eq!((hand.len(), hand.hand_sum()), (1, HandSum::NoWin(10)));
+    assert_eq!(hand.cards(), &[Card::new(Suit::Clubs, 10)]);
+}
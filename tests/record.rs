@@ -0,0 +1,44 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, Game, GameRules, ParseRecordError, RandomStrategy, RoundResult, Suit};
+use rand::SeedableRng;
+
+#[test]
+fn records_round_trip() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut strategy = RandomStrategy::from_seed([9; 16]);
+    let mut g = Game::new(5, 3);
+    g.shuffle_with_rng(&mut rng);
+    let results = g.run(&mut rng, &mut strategy, 300).unwrap();
+    assert!(results.iter().any(|r| r.reshuffled));
+    for result in results {
+        assert_eq!(RoundResult::from_record(&result.to_record()), Ok(result));
+    }
+}
+
+#[test]
+fn malformed_records() {
+    assert_eq!(RoundResult::from_record("1,\u{2660}A,2"), Err(ParseRecordError::FieldCount(3)));
+    assert_eq!(RoundResult::from_record("1,\u{2660}A,2,-").map(|r| r.reshuffled), Ok(false));
+    assert_eq!(RoundResult::from_record("x,\u{2660}A,2,-"),
+               Err(ParseRecordError::InvalidPlayer("x".to_owned())));
+    assert_eq!(RoundResult::from_record("1,\u{2660}A,2,Bogus"),
+               Err(ParseRecordError::InvalidWin("Bogus".to_owned())));
+}
+
+#[test]
+fn records_round_trip_with_custom_specials() {
+    let rules = GameRules { specials: vec![(Suit::Hearts, 7)], ..GameRules::default() };
+    let mut rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut strategy = RandomStrategy::from_seed([9; 16]);
+    let mut g = Game::with_rules(5, 1, 3, rules.clone());
+    g.shuffle_with_rng(&mut rng);
+    let results = g.run(&mut rng, &mut strategy, 300).unwrap();
+    assert!(results.iter().any(|r| r.card == Card::Special(Suit::Hearts, 7)));
+    for result in results {
+        assert_eq!(RoundResult::from_record_with(&result.to_record(), &rules), Ok(result));
+    }
+    assert_eq!(Card::parse_with("\u{2665}7", &rules.specials), Ok(Card::Special(Suit::Hearts, 7)));
+    assert_eq!("\u{2665}7".parse(), Ok(Card::Regular(Suit::Hearts, 7)));
+}
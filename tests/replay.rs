@@ -0,0 +1,109 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Card, Game, GameObserver, GameRules, Hand, RandomStrategy, ReplayError, RoundResult,
+             Strategy, Suit};
+use rand::SeedableRng;
+
+fn played() -> (Game, Game) {
+    let mut rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut g = Game::new(5, 3);
+    g.shuffle_with_rng(&mut rng);
+    let initial = g.clone();
+    g.run(&mut rng, &mut RandomStrategy::from_seed([9; 16]), 300).unwrap();
+    (initial, g)
+}
+
+#[test]
+fn replaying_records_reaches_the_same_position() {
+    let (initial, g) = played();
+    assert!(g.history().iter().any(|r| r.reshuffled));
+    let records: Vec<RoundResult> = g.history().iter()
+        .map(|r| RoundResult::from_record(&r.to_record()).unwrap())
+        .collect();
+    let replayed = Game::replay(initial, &records).unwrap();
+    assert_eq!(replayed.position_key(), g.position_key());
+    assert_eq!(replayed.hands(), g.hands());
+    assert_eq!((replayed.wins(), replayed.scores()), (g.wins(), g.scores()));
+    assert_eq!(replayed.history(), g.history());
+    assert_eq!(replayed.remaining_cards(), g.remaining_cards());
+}
+
+#[test]
+fn replay_detects_divergence() {
+    let (initial, g) = played();
+    let mut records = g.history().to_vec();
+    records.swap(3, 4);
+    match Game::replay(initial.clone(), &records) {
+        Err(ReplayError::Giver { index: 3, .. }) => {},
+        other => panic!("unexpected {:?}", other),
+    }
+    let mut records = g.history().to_vec();
+    records[0].card = records[1].card;
+    assert_eq!(Game::replay(initial.clone(), &records),
+               Err(ReplayError::Card { index: 0, recorded: records[1].card }));
+    let mut records = g.history().to_vec();
+    records[0].reshuffled = true;
+    assert_eq!(Game::replay(initial, &records),
+               Err(ReplayError::Reshuffle { index: 0, recorded: true }));
+}
+
+#[test]
+fn replaying_records_with_custom_specials() {
+    let rules = GameRules { specials: vec![(Suit::Hearts, 7)], ..GameRules::default() };
+    let mut rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut g = Game::with_rules(5, 1, 3, rules.clone());
+    g.shuffle_with_rng(&mut rng);
+    let initial = g.clone();
+    g.run(&mut rng, &mut RandomStrategy::from_seed([9; 16]), 300).unwrap();
+    let records: Vec<RoundResult> = g.history().iter()
+        .map(|r| RoundResult::from_record_with(&r.to_record(), &rules).unwrap())
+        .collect();
+    let replayed = Game::replay(initial, &records).unwrap();
+    assert_eq!(replayed.hands(), g.hands());
+    assert_eq!(replayed.history(), g.history());
+}
+
+struct Stubborn(usize);
+
+impl Strategy for Stubborn {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, _eligible: &[usize])
+            -> usize {
+        self.0
+    }
+}
+
+struct Reshuffles(usize);
+
+impl GameObserver for Reshuffles {
+    fn on_reshuffle(&mut self) {
+        self.0 += 1;
+    }
+}
+
+#[test]
+fn replaying_after_a_rejected_choice_on_a_reshuffle() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut strategy = RandomStrategy::from_seed([9; 16]);
+    let mut g = Game::new(3, 0);
+    g.shuffle_with_rng(&mut rng);
+    let initial = g.clone();
+    let mut reshuffles = Reshuffles(0);
+    let mut rejected_reshuffles = 0;
+    for _ in 0..2000 {
+        let discarded = g.discard().len();
+        if g.step_observed(&mut rng, &mut Stubborn(99), &mut reshuffles).is_ok() {
+            continue;
+        }
+        if discarded > 0 && g.discard().is_empty() {
+            rejected_reshuffles += 1;
+        }
+        g.step_observed(&mut rng, &mut strategy, &mut reshuffles).unwrap();
+    }
+    assert!(rejected_reshuffles > 0);
+    let recorded = g.history().iter().filter(|r| r.reshuffled).count();
+    assert_eq!(reshuffles.0, recorded);
+    let replayed = Game::replay(initial, g.history()).unwrap();
+    assert_eq!(replayed.hands(), g.hands());
+    assert_eq!(replayed.history(), g.history());
+}
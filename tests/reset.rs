@@ -0,0 +1,41 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Game, RandomStrategy};
+use rand::SeedableRng;
+
+#[test]
+fn reset_matches_a_fresh_game() {
+    let mut strategy = RandomStrategy::from_seed([1; 16]);
+    let mut g = Game::new(4, 2);
+    g.shuffle_with_rng(&mut rand::prng::XorShiftRng::from_seed([2; 16]));
+    g.run(&mut rand::prng::XorShiftRng::from_seed([3; 16]), &mut strategy, 200).unwrap();
+
+    g.reset_with_rng(&mut rand::prng::XorShiftRng::from_seed([4; 16]));
+    let mut fresh = Game::new(4, 2);
+    fresh.shuffle_with_rng(&mut rand::prng::XorShiftRng::from_seed([4; 16]));
+    assert_eq!(g, fresh);
+}
+
+#[test]
+fn reset_reuses_allocations() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([5; 16]);
+    let mut strategy = RandomStrategy::from_seed([6; 16]);
+    let mut g = Game::new(4, 2);
+    g.shuffle_with_rng(&mut rng);
+    g.run(&mut rng, &mut strategy, 500).unwrap();
+    let history = g.history().as_ptr();
+    let capacities = |g: &Game| {
+        let hands: Vec<usize> = g.hands().iter().map(|h| h.capacity()).collect();
+        (g.deck_capacity(), g.discard().capacity(), hands)
+    };
+    let before = capacities(&g);
+    assert!(before.0 >= 54 && before.1 >= 54 && before.2.iter().all(|&c| c >= 5));
+    for _ in 0..10 {
+        g.reset_with_rng(&mut rng);
+        assert_eq!(capacities(&g), before);
+        g.run(&mut rng, &mut strategy, 500).unwrap();
+        assert_eq!(g.history().as_ptr(), history);
+        assert_eq!(capacities(&g), before);
+    }
+}
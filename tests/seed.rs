@@ -0,0 +1,12 @@
+extern crate prugel;
+
+use prugel::seed_from_str;
+
+#[test]
+fn seed_from_str_is_stable() {
+    assert_eq!(seed_from_str(""), [0x25, 0x23, 0x22, 0x84, 0xe4, 0x9c, 0xf2, 0xcb,
+                                   0x25, 0x23, 0x22, 0x84, 0xe4, 0x9c, 0xf2, 0xcb]);
+    assert_eq!(seed_from_str("experiment-3"), [0x5a, 0x9b, 0x03, 0xb6, 0x00, 0x16, 0x2a, 0x3f,
+                                               0xb9, 0x1e, 0x09, 0xc5, 0x8b, 0x55, 0x1f, 0x13]);
+    assert_ne!(seed_from_str("experiment-3"), seed_from_str("experiment-4"));
+}
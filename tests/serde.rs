@@ -0,0 +1,50 @@
+#![cfg(feature = "serde")]
+
+extern crate prugel;
+extern crate rand;
+extern crate serde_json;
+
+use prugel::{Card, Game, InvariantError, RandomStrategy, Suit};
+use rand::SeedableRng;
+
+#[test]
+fn game_json_round_trip() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([7; 16]);
+    let mut strategy = RandomStrategy::from_seed([60; 16]);
+    let mut g = Game::new(4, 2);
+    g.shuffle_with_rng(&mut rng);
+    for _ in 0..40 {
+        g.step_with_rng(&mut rng, &mut strategy).unwrap().played().unwrap();
+    }
+    let json = serde_json::to_string(&g).unwrap();
+    let back: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, g);
+}
+
+#[test]
+fn special_and_joker_cards_round_trip() {
+    for &card in &[Card::Joker(2), Card::Special(Suit::Diamonds, 12), Card::Regular(Suit::Clubs, 7)] {
+        let json = serde_json::to_string(&card).unwrap();
+        assert_eq!(serde_json::from_str::<Card>(&json).unwrap(), card);
+    }
+}
+
+#[test]
+fn validate_catches_a_lost_card() {
+    let g = Game::new(3, 0);
+    let mut json: serde_json::Value = serde_json::to_value(&g).unwrap();
+    json["deck"]["cards"].as_array_mut().unwrap().pop();
+    let broken: Game = serde_json::from_value(json).unwrap();
+    assert_eq!(broken.validate(), Err(InvariantError::CardCount { expected: 52, found: 51 }));
+}
+
+#[test]
+fn remaining_by_rank_skips_malformed_ranks() {
+    let g = Game::new(3, 0);
+    let mut json: serde_json::Value = serde_json::to_value(&g).unwrap();
+    let bad = serde_json::json!({"Regular": ["Clubs", 14]});
+    json["deck"]["cards"].as_array_mut().unwrap().push(bad);
+    let broken: Game = serde_json::from_value(json).unwrap();
+    assert_eq!(broken.remaining_by_rank(), g.remaining_by_rank());
+    assert_eq!(broken.remaining_by_suit(), [13, 13, 14, 13]);
+}
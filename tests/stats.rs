@@ -0,0 +1,17 @@
+extern crate prugel;
+
+use prugel::{GameRules, SimulationStats, WinCondition};
+
+#[test]
+fn simulation_tallies_are_consistent() {
+    let stats = SimulationStats::run(4, 2, GameRules::default(), 50, 1);
+    assert_eq!(stats.games(), 50);
+    assert_eq!(stats.wins(), 50);
+    assert_eq!(stats.player_wins().iter().sum::<u64>(), stats.wins());
+    let by_condition: u64 = [WinCondition::FiveCards, WinCondition::TwentyFive,
+                             WinCondition::Special, WinCondition::Joker]
+        .iter().map(|&w| stats.condition_count(w)).sum();
+    assert_eq!(by_condition, stats.wins());
+    assert!(stats.average_rounds_to_win().unwrap() >= 1.0);
+    assert_eq!(stats, SimulationStats::run(4, 2, GameRules::default(), 50, 1));
+}
@@ -0,0 +1,170 @@
+extern crate prugel;
+extern crate rand;
+
+use std::io::Cursor;
+
+use prugel::{Bias, BiasedRandomStrategy, Card, ExpectimaxStrategy, Game, GameObserver,
+             GameRules, GreedyStrategy, Hand, HumanStrategy, PrugelError, PlayerStrategies,
+             RandomStrategy, StepError, Strategy, Suit};
+use rand::SeedableRng;
+
+fn clubs(ranks: &[u8]) -> Hand {
+    let cards: Vec<Card> = ranks.iter().map(|&n| Card::new(Suit::Clubs, n)).collect();
+    Hand::from_cards_unchecked(&cards)
+}
+
+#[test]
+fn expectimax_takes_wins_and_withholds_them() {
+    let mut strategy = ExpectimaxStrategy::new(GameRules::default(), 0);
+    let hearts5 = Card::new(Suit::Hearts, 5);
+    let hands = vec![clubs(&[10]), clubs(&[10, 10]), clubs(&[2])];
+    assert_eq!(strategy.choose(0, &hands, hearts5, &[0, 1, 2]), 0);
+    let hands = vec![clubs(&[2]), clubs(&[10, 10]), clubs(&[3])];
+    assert_eq!(strategy.choose(1, &hands, hearts5, &[0, 1, 2]), 1);
+    assert_eq!(strategy.choose(0, &hands, hearts5, &[1, 2]), 2);
+}
+
+#[test]
+fn expectimax_looks_ahead() {
+    // A fourth card for player 0, who gives next, lets almost any draw win
+    // them five cards. Without lookahead every option ties.
+    let hearts2 = Card::new(Suit::Hearts, 2);
+    let hands = vec![clubs(&[2, 2, 2]), clubs(&[3]), clubs(&[4])];
+    let mut shallow = ExpectimaxStrategy::new(GameRules::default(), 0);
+    assert_eq!(shallow.choose(2, &hands, hearts2, &[0, 1, 2]), 0);
+    let mut deep = ExpectimaxStrategy::new(GameRules::default(), 1);
+    assert!(deep.choose(2, &hands, hearts2, &[0, 1, 2]) != 0);
+}
+
+#[test]
+fn strategies_take_array_backed_hands() {
+    let hands = [clubs(&[10]), clubs(&[10, 10])];
+    let card = Card::new(Suit::Hearts, 5);
+    assert!(RandomStrategy::default().choose(0, &hands, card, &[0, 1]) < 2);
+    assert_eq!(GreedyStrategy::default().choose(0, &hands, card, &[0, 1]), 0);
+}
+
+struct Stubborn(usize);
+
+impl Strategy for Stubborn {
+    fn choose(&mut self, _giver: usize, _hands: &[Hand], _card: Card, _eligible: &[usize])
+            -> usize {
+        self.0
+    }
+}
+
+#[test]
+fn try_choose_checks_the_answer() {
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4])];
+    let card = Card::new(Suit::Hearts, 5);
+    assert_eq!(Stubborn(2).try_choose(0, &hands, card, &[0, 2]), Ok(2));
+    assert_eq!(Stubborn(1).try_choose(0, &hands, card, &[0, 2]),
+               Err(PrugelError::IneligibleChoice(1)));
+    assert_eq!(Stubborn(0).try_choose(0, &hands, card, &[]), Err(PrugelError::NoEligiblePlayers));
+}
+
+#[test]
+fn step_reports_an_ineligible_choice() {
+    // The top card of an unshuffled deck is the king of diamonds, which
+    // every player may take.
+    let mut g = Game::new(3, 0);
+    let before = g.clone();
+    let mut rng = rand::prng::XorShiftRng::from_seed([1; 16]);
+    let err = g.step_with_rng(&mut rng, &mut Stubborn(5)).unwrap_err();
+    assert_eq!(err, StepError::InvalidStrategyChoice { returned: 5, eligible: vec![0, 1, 2] });
+    assert_eq!(g, before);
+    let mut draws = Draws(Vec::new());
+    assert!(g.step_observed(&mut rng, &mut Stubborn(7), &mut draws).is_err());
+    assert_eq!(draws.0, vec![]);
+    assert!(g.step_observed(&mut rng, &mut Stubborn(1), &mut draws).is_ok());
+    assert_eq!(draws.0, vec![Card::new(Suit::Diamonds, 13)]);
+}
+
+struct Draws(Vec<Card>);
+
+impl GameObserver for Draws {
+    fn on_draw(&mut self, card: Card) {
+        self.0.push(card);
+    }
+}
+
+#[test]
+fn player_strategies_follow_the_giver() {
+    let mut strategies = PlayerStrategies::new(vec![Box::new(Stubborn(2)), Box::new(Stubborn(0))]);
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4])];
+    let card = Card::new(Suit::Hearts, 5);
+    assert_eq!(strategies.choose(0, &hands, card, &[0, 1, 2]), 2);
+    assert_eq!(strategies.choose(1, &hands, card, &[0, 1, 2]), 0);
+}
+
+#[test]
+fn random_strategy_picks_an_eligible_player() {
+    let mut strategy = RandomStrategy::from_seed([3; 16]);
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4]), clubs(&[5]), clubs(&[6])];
+    let card = Card::new(Suit::Hearts, 5);
+    let eligible = [1, 3, 4];
+    let mut seen = [false; 5];
+    for _ in 0..200 {
+        let j = strategy.choose(0, &hands, card, &eligible);
+        assert!(eligible.contains(&j));
+        seen[j] = true;
+    }
+    assert_eq!(seen, [false, true, false, true, true]);
+}
+
+#[test]
+fn uniform_bias_is_random_strategy() {
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4]), clubs(&[5])];
+    let card = Card::new(Suit::Hearts, 5);
+    let rng = rand::prng::XorShiftRng::from_seed([8; 16]);
+    let mut biased = BiasedRandomStrategy::new(rng, Bias::Uniform, GameRules::default());
+    let mut random = RandomStrategy::from_seed([8; 16]);
+    for _ in 0..100 {
+        assert_eq!(biased.choose(0, &hands, card, &[0, 2, 3]),
+                   random.choose(0, &hands, card, &[0, 2, 3]));
+    }
+}
+
+#[test]
+fn biases_favour_their_players() {
+    // Sums 0 and 20 weigh 1 and 21; seats 1 and 2 away from the giver
+    // weigh 2 and 3.
+    let hands = [Hand::new(), clubs(&[10, 10]), clubs(&[2]), clubs(&[3]), clubs(&[4])];
+    let card = Card::new(Suit::Hearts, 5);
+    let cases = [(Bias::HighSums, [0, 1], 1), (Bias::FarSeats, [1, 2], 2)];
+    for &(bias, eligible, favourite) in cases.iter() {
+        let rng = rand::prng::XorShiftRng::from_seed([9; 16]);
+        let mut strategy = BiasedRandomStrategy::new(rng, bias, GameRules::default());
+        let mut counts = [0; 5];
+        for _ in 0..1000 {
+            let j = strategy.choose(0, &hands, card, &eligible);
+            assert!(eligible.contains(&j));
+            counts[j] += 1;
+        }
+        assert!(counts[favourite] > 550, "{:?}: {:?}", bias, counts);
+    }
+}
+
+#[test]
+fn human_strategy_asks_again_until_the_answer_is_eligible() {
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4])];
+    let card = Card::new(Suit::Hearts, 5);
+    let mut output = Vec::new();
+    let choice = {
+        let input = Cursor::new(&b"9\nabc\n2\n"[..]);
+        let mut human = HumanStrategy::new(input, &mut output, GameRules::default());
+        human.choose(0, &hands, card, &[0, 2])
+    };
+    assert_eq!(choice, 2);
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("Player 0 gives \u{2665}5. Who receives it?\n  0: 2\n  2: 4\n"));
+    assert_eq!(output.matches("Please enter one of [0, 2]").count(), 2);
+}
+
+#[test]
+fn human_strategy_falls_back_to_the_first_eligible_on_eof() {
+    let hands = [clubs(&[2]), clubs(&[3]), clubs(&[4])];
+    let card = Card::new(Suit::Hearts, 5);
+    let mut human = HumanStrategy::new(Cursor::new(&b""[..]), Vec::new(), GameRules::default());
+    assert_eq!(human.choose(0, &hands, card, &[1, 2]), 1);
+}
@@ -0,0 +1,22 @@
+extern crate prugel;
+
+use prugel::{FirstEligibleStrategy, GreedyStrategy, RandomStrategy, Strategy, Tournament};
+
+fn field() -> Vec<Box<dyn Strategy>> {
+    vec![Box::new(GreedyStrategy::default()), Box::new(RandomStrategy::default()),
+         Box::new(FirstEligibleStrategy)]
+}
+
+#[test]
+fn tournament_counts_each_strategys_wins() {
+    let mut t = Tournament::new(field()).jokers(2);
+    t.run(30, 7).unwrap();
+    t.run(30, 37).unwrap();
+    assert_eq!(t.games(), 60);
+    assert!(t.wins().iter().sum::<u64>() <= 60);
+    assert!(t.wins().iter().sum::<u64>() > 0);
+
+    let mut again = Tournament::new(field()).jokers(2);
+    again.run(60, 7).unwrap();
+    assert_eq!(again.wins(), t.wins());
+}
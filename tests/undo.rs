@@ -0,0 +1,38 @@
+extern crate prugel;
+extern crate rand;
+
+use prugel::{Game, RandomStrategy};
+use rand::SeedableRng;
+
+#[test]
+fn undo_restores_previous_states() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([3; 16]);
+    let mut strategy = RandomStrategy::from_seed([4; 16]);
+    let mut g = Game::new(4, 3);
+    g.shuffle_with_rng(&mut rng);
+    let mut states = Vec::new();
+    // The deck has 55 cards, so the first 55 steps never reshuffle.
+    for _ in 0..55 {
+        let before = g.clone();
+        states.push((before, g.step_with_rng(&mut rng, &mut strategy).unwrap().played().unwrap()));
+    }
+    while let Some((before, result)) = states.pop() {
+        assert_eq!(g.undo(), Some(result));
+        assert_eq!(g, before);
+    }
+    assert_eq!(g.undo(), None);
+}
+
+#[test]
+fn undo_stops_at_reshuffle() {
+    let mut rng = rand::prng::XorShiftRng::from_seed([3; 16]);
+    let mut strategy = RandomStrategy::from_seed([4; 16]);
+    let mut g = Game::new(4, 0);
+    g.shuffle_with_rng(&mut rng);
+    for _ in 0..53 {
+        g.step_with_rng(&mut rng, &mut strategy).unwrap().played().unwrap();
+    }
+    let reshuffles: Vec<usize> = (0..53).filter(|&i| g.history()[i].reshuffled).collect();
+    assert_eq!(reshuffles, vec![52]);
+    assert_eq!(g.undo(), None);
+}